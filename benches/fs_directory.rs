@@ -0,0 +1,207 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tantivy::collector::{Count, TopDocs};
+use tantivy::directory::error::{DeleteError, OpenReadError, OpenWriteError};
+use tantivy::directory::{
+    FileHandle, FsDirectory, MmapDirectory, WatchCallback, WatchHandle, WritePtr,
+};
+use tantivy::query::{Query, QueryParser};
+use tantivy::schema::{Schema, STORED, TEXT};
+use tantivy::{doc, Directory, HasLen, Index, IndexReader, ReloadPolicy};
+use tempfile::TempDir;
+
+const ALICE_TXT: &'static str = include_str!("alice.txt");
+
+const QUERIES: [&'static str; 4] = ["alice", "queen AND king", "\"white rabbit\"", "cat OR dog"];
+
+/// Indexes every paragraph of alice.txt as a document, in a directory on disk.
+fn build_index_on_disk() -> TempDir {
+    let tempdir = TempDir::new().unwrap();
+    let mut schema_builder = Schema::builder();
+    let text = schema_builder.add_text_field("text", TEXT | STORED);
+    let schema = schema_builder.build();
+    let directory = MmapDirectory::open(tempdir.path()).unwrap();
+    let index = Index::create(directory, schema).unwrap();
+    let mut index_writer = index.writer_with_num_threads(1, 50_000_000).unwrap();
+    for _ in 0..10 {
+        for paragraph in ALICE_TXT.split("\n\n") {
+            index_writer.add_document(doc!(text => paragraph));
+        }
+    }
+    index_writer.commit().unwrap();
+    index_writer.wait_merging_threads().unwrap();
+    tempdir
+}
+
+/// Counts the reads an `FsDirectory` issues to the filesystem.
+#[derive(Default)]
+struct ReadStats {
+    num_reads: AtomicU64,
+    num_bytes: AtomicU64,
+}
+
+impl ReadStats {
+    fn snapshot(&self) -> (u64, u64) {
+        (
+            self.num_reads.load(Ordering::Relaxed),
+            self.num_bytes.load(Ordering::Relaxed),
+        )
+    }
+}
+
+fn observed_fs_directory(path: &Path, read_stats: &Arc<ReadStats>) -> FsDirectory {
+    let read_stats = read_stats.clone();
+    FsDirectory::new(path).with_read_observer(Arc::new(move |_path: &Path, from: u64, to: u64| {
+        read_stats.num_reads.fetch_add(1, Ordering::Relaxed);
+        read_stats.num_bytes.fetch_add(to - from, Ordering::Relaxed);
+    }))
+}
+
+/// `FsDirectory` reading every file ahead, in as few reads as possible,
+/// as soon as it is opened.
+#[derive(Clone, Debug)]
+struct ReadAheadDirectory(FsDirectory);
+
+impl Directory for ReadAheadDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Box<dyn FileHandle>, OpenReadError> {
+        let file_handle = self.0.get_file_handle(path)?;
+        file_handle
+            .prefetch(0, file_handle.len())
+            .map_err(|io_error| OpenReadError::wrap_io_error(io_error, path.to_path_buf()))?;
+        Ok(file_handle)
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        self.0.delete(path)
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        self.0.exists(path)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        self.0.open_write(path)
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        self.0.atomic_read(path)
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.0.atomic_write(path, data)
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> tantivy::Result<WatchHandle> {
+        self.0.watch(watch_callback)
+    }
+}
+
+fn open_reader(index: &Index) -> IndexReader {
+    index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .unwrap()
+}
+
+fn parse_queries(index: &Index) -> Vec<Box<dyn Query>> {
+    let text = index.schema().get_field("text").unwrap();
+    let query_parser = QueryParser::for_index(index, vec![text]);
+    QUERIES
+        .iter()
+        .map(|query| query_parser.parse_query(query).unwrap())
+        .collect()
+}
+
+fn run_queries(reader: &IndexReader, queries: &[Box<dyn Query>]) {
+    let searcher = reader.searcher();
+    for query in queries {
+        let (count, top_docs) = searcher
+            .search(query.as_ref(), &(Count, TopDocs::with_limit(10)))
+            .unwrap();
+        assert!(count >= top_docs.len());
+        for (_score, doc_address) in top_docs {
+            searcher.doc(doc_address).unwrap();
+        }
+    }
+}
+
+fn run_cold<D: Directory>(directory: D) {
+    let index = Index::open(directory).unwrap();
+    let queries = parse_queries(&index);
+    run_queries(&open_reader(&index), &queries);
+}
+
+/// Runs `workload` once, and prints the reads it issued to the filesystem.
+fn report_reads(
+    name: &str,
+    fs_directory: &FsDirectory,
+    read_stats: &ReadStats,
+    workload: impl FnOnce(),
+) {
+    let (num_reads_before, num_bytes_before) = read_stats.snapshot();
+    let num_cache_hits_before = fs_directory.num_cache_hits();
+    workload();
+    let (num_reads, num_bytes) = read_stats.snapshot();
+    println!(
+        "{}: {} reads, {} bytes read, {} cache hits",
+        name,
+        num_reads - num_reads_before,
+        num_bytes - num_bytes_before,
+        fs_directory.num_cache_hits() - num_cache_hits_before
+    );
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let tempdir = build_index_on_disk();
+    let path: PathBuf = tempdir.path().to_path_buf();
+    let mut group = c.benchmark_group("fs-directory-search");
+
+    // Every iteration opens the index from scratch: nothing is cached.
+    let read_stats = Arc::new(ReadStats::default());
+    let fs_directory = observed_fs_directory(&path, &read_stats);
+    report_reads("fs-cold", &fs_directory, &read_stats, || {
+        run_cold(fs_directory.clone())
+    });
+    group.bench_function("fs-cold", |b| b.iter(|| run_cold(FsDirectory::new(&path))));
+
+    // Same as `fs-cold`, but every file is read ahead when it is opened.
+    let read_stats = Arc::new(ReadStats::default());
+    let fs_directory = observed_fs_directory(&path, &read_stats);
+    report_reads("fs-cold-read-ahead", &fs_directory, &read_stats, || {
+        run_cold(ReadAheadDirectory(fs_directory.clone()))
+    });
+    group.bench_function("fs-cold-read-ahead", |b| {
+        b.iter(|| run_cold(ReadAheadDirectory(FsDirectory::new(&path))))
+    });
+
+    // The searcher is reused: reads are served by the per-chunk cache of `FSFile`.
+    let read_stats = Arc::new(ReadStats::default());
+    let fs_directory = observed_fs_directory(&path, &read_stats);
+    let index = Index::open(fs_directory.clone()).unwrap();
+    let queries = parse_queries(&index);
+    let reader = open_reader(&index);
+    run_queries(&reader, &queries);
+    report_reads("fs-warm-chunk-cache", &fs_directory, &read_stats, || {
+        run_queries(&reader, &queries)
+    });
+    group.bench_function("fs-warm-chunk-cache", |b| {
+        b.iter(|| run_queries(&reader, &queries))
+    });
+
+    // Baseline: the same index, memory mapped.
+    let mmap_index = Index::open_in_dir(&path).unwrap();
+    let mmap_queries = parse_queries(&mmap_index);
+    let mmap_reader = open_reader(&mmap_index);
+    group.bench_function("mmap-warm", |b| {
+        b.iter(|| run_queries(&mmap_reader, &mmap_queries))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
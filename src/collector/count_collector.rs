@@ -82,6 +82,9 @@ mod tests {
     use super::{Count, SegmentCountCollector};
     use crate::collector::Collector;
     use crate::collector::SegmentCollector;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{Index, Term};
 
     #[test]
     fn test_count_collect_does_not_requires_scoring() {
@@ -111,4 +114,25 @@ mod tests {
             assert_eq!(count_collector.harvest(), 2);
         }
     }
+
+    #[test]
+    fn test_count_across_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>"b"));
+        index_writer.add_document(doc!(text=>"a b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>"c"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let term_query =
+            TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        assert_eq!(searcher.search(&term_query, &Count)?, 3);
+        Ok(())
+    }
 }
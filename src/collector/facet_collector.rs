@@ -461,7 +461,7 @@ mod tests {
     use crate::collector::Count;
     use crate::core::Index;
     use crate::query::{AllQuery, QueryParser, TermQuery};
-    use crate::schema::{Document, Facet, Field, IndexRecordOption, Schema};
+    use crate::schema::{Document, Facet, Field, IndexRecordOption, Schema, TEXT};
     use crate::Term;
     use rand::distributions::Uniform;
     use rand::prelude::SliceRandom;
@@ -602,6 +602,46 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_facet_collector_counts_filtered_docs() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let category = schema_builder.add_facet_field("category");
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let docs = [
+            ("red shoe", "/shoes"),
+            ("red hat", "/hats"),
+            ("blue shoe", "/shoes"),
+            ("red shoe", "/shoes"),
+            ("red scarf", "/scarves"),
+            ("blue hat", "/hats"),
+        ];
+        for &(text_val, category_val) in &docs {
+            index_writer.add_document(doc!(
+                text => text_val,
+                category => Facet::from(category_val),
+            ));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let mut facet_collector = FacetCollector::for_field(category);
+        facet_collector.add_facet("/");
+        let red = TermQuery::new(Term::from_field_text(text, "red"), IndexRecordOption::Basic);
+        let counts = searcher.search(&red, &facet_collector)?;
+        let facets: Vec<(&Facet, u64)> = counts.get("/").collect();
+        assert_eq!(
+            facets,
+            vec![
+                (&Facet::from("/hats"), 1),
+                (&Facet::from("/scarves"), 1),
+                (&Facet::from("/shoes"), 2),
+            ]
+        );
+        assert_eq!(counts.top_k("/", 1), vec![(&Facet::from("/shoes"), 2)]);
+        Ok(())
+    }
+
     #[test]
     fn test_non_used_facet_collector() {
         let mut facet_collector = FacetCollector::for_field(Field::from_field_id(0));
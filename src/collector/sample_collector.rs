@@ -0,0 +1,220 @@
+use super::{Collector, SegmentCollector};
+use crate::{DocAddress, DocId, Score, SegmentLocalId, SegmentReader};
+use std::collections::BinaryHeap;
+
+/// `SampleCollector` collects a uniform random sample of the documents
+/// matching a query.
+///
+/// It returns `min(sample_size, num_matching_docs)` distinct documents,
+/// sorted by `DocAddress`, in a single pass over the matching documents
+/// and using `O(sample_size)` memory.
+///
+/// Sampling relies on random tags: each matching document is assigned a
+/// pseudo-random key derived from the seed and its `DocAddress`, and the
+/// `sample_size` documents with the lowest keys are kept. This is the
+/// parallel-friendly flavor of reservoir sampling: the reservoirs of different
+/// segments can be merged by simply keeping the lowest keys overall.
+///
+/// For a given seed and a given searcher, the sample is deterministic.
+///
+/// ```rust
+/// use tantivy::collector::SampleCollector;
+/// use tantivy::query::AllQuery;
+/// use tantivy::schema::{Schema, TEXT};
+/// use tantivy::{doc, Index};
+///
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let schema = schema_builder.build();
+/// let index = Index::create_in_ram(schema);
+///
+/// let mut index_writer = index.writer(3_000_000).unwrap();
+/// for i in 0..100 {
+///     index_writer.add_document(doc!(title => format!("Book {}", i)));
+/// }
+/// assert!(index_writer.commit().is_ok());
+///
+/// let searcher = index.reader().unwrap().searcher();
+/// let sample = searcher.search(&AllQuery, &SampleCollector::with_size(10, 42)).unwrap();
+/// assert_eq!(sample.len(), 10);
+/// ```
+pub struct SampleCollector {
+    sample_size: usize,
+    seed: u64,
+}
+
+impl SampleCollector {
+    /// Creates a `SampleCollector` returning (at most) `sample_size` documents.
+    ///
+    /// The `seed` drives the pseudo-random selection of the documents.
+    pub fn with_size(sample_size: usize, seed: u64) -> SampleCollector {
+        SampleCollector { sample_size, seed }
+    }
+}
+
+// splitmix64 finalizer.
+fn mix(mut val: u64) -> u64 {
+    val = (val ^ (val >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    val = (val ^ (val >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    val ^ (val >> 31)
+}
+
+fn sample_key(seed: u64, doc_address: DocAddress) -> u64 {
+    let DocAddress(segment_ord, doc) = doc_address;
+    mix(mix(seed.wrapping_add(segment_ord as u64)) ^ doc as u64)
+}
+
+/// Keeps the `sample_size` entries with the lowest keys.
+struct Reservoir {
+    sample_size: usize,
+    // max-heap: the top is the entry to evict first.
+    heap: BinaryHeap<(u64, DocAddress)>,
+}
+
+impl Reservoir {
+    /// `max_num_entries` is an upper bound on the number of entries
+    /// that will be pushed, used to avoid over-allocating for large sample sizes.
+    fn new(sample_size: usize, max_num_entries: usize) -> Reservoir {
+        Reservoir {
+            sample_size,
+            heap: BinaryHeap::with_capacity(sample_size.min(max_num_entries)),
+        }
+    }
+
+    fn push(&mut self, key: u64, doc_address: DocAddress) {
+        if self.heap.len() < self.sample_size {
+            self.heap.push((key, doc_address));
+        } else if let Some(mut head) = self.heap.peek_mut() {
+            if (key, doc_address) < *head {
+                *head = (key, doc_address);
+            }
+        }
+    }
+}
+
+impl Collector for SampleCollector {
+    type Fruit = Vec<DocAddress>;
+    type Child = SampleSegmentCollector;
+
+    fn for_segment(
+        &self,
+        segment_local_id: SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> crate::Result<SampleSegmentCollector> {
+        Ok(SampleSegmentCollector {
+            segment_local_id,
+            seed: self.seed,
+            reservoir: Reservoir::new(self.sample_size, segment.max_doc() as usize),
+        })
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<Vec<(u64, DocAddress)>>,
+    ) -> crate::Result<Vec<DocAddress>> {
+        let num_entries = segment_fruits.iter().map(Vec::len).sum();
+        let mut reservoir = Reservoir::new(self.sample_size, num_entries);
+        for (key, doc_address) in segment_fruits.into_iter().flatten() {
+            reservoir.push(key, doc_address);
+        }
+        let mut sample: Vec<DocAddress> = reservoir
+            .heap
+            .into_iter()
+            .map(|(_, doc_address)| doc_address)
+            .collect();
+        sample.sort();
+        Ok(sample)
+    }
+}
+
+pub struct SampleSegmentCollector {
+    segment_local_id: SegmentLocalId,
+    seed: u64,
+    reservoir: Reservoir,
+}
+
+impl SegmentCollector for SampleSegmentCollector {
+    type Fruit = Vec<(u64, DocAddress)>;
+
+    fn collect(&mut self, doc: DocId, _score: Score) {
+        let doc_address = DocAddress(self.segment_local_id, doc);
+        let key = sample_key(self.seed, doc_address);
+        self.reservoir.push(key, doc_address);
+    }
+
+    fn harvest(self) -> Vec<(u64, DocAddress)> {
+        self.reservoir.heap.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SampleCollector;
+    use crate::query::{AllQuery, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, INDEXED};
+    use crate::{DocAddress, Index, Term};
+    use std::collections::HashSet;
+
+    fn create_index(num_segments: u64, docs_per_segment: u64) -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let num = schema_builder.add_u64_field("num", INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for segment in 0..num_segments {
+            for i in 0..docs_per_segment {
+                index_writer.add_document(doc!(num => (segment * docs_per_segment + i) % 2));
+            }
+            index_writer.commit()?;
+        }
+        Ok(index)
+    }
+
+    #[test]
+    fn test_sample_collector_size() -> crate::Result<()> {
+        let index = create_index(3, 50)?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 3);
+        for &sample_size in &[0, 1, 10, 149, 150, 1_000, usize::MAX] {
+            let sample = searcher.search(&AllQuery, &SampleCollector::with_size(sample_size, 7))?;
+            assert_eq!(sample.len(), sample_size.min(150));
+            let distinct: HashSet<DocAddress> = sample.iter().cloned().collect();
+            assert_eq!(distinct.len(), sample.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_collector_only_samples_matching_docs() -> crate::Result<()> {
+        let index = create_index(2, 50)?;
+        let searcher = index.reader()?.searcher();
+        let num = index.schema().get_field("num").unwrap();
+        let query = TermQuery::new(Term::from_field_u64(num, 1), IndexRecordOption::Basic);
+        let sample = searcher.search(&query, &SampleCollector::with_size(20, 7))?;
+        assert_eq!(sample.len(), 20);
+        for doc_address in sample {
+            let DocAddress(segment_ord, doc) = doc_address;
+            assert_eq!(doc % 2, 1, "segment {}", segment_ord);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_sample_collector_reproducible() -> crate::Result<()> {
+        let index = create_index(3, 50)?;
+        let searcher = index.reader()?.searcher();
+        let sample = searcher.search(&AllQuery, &SampleCollector::with_size(10, 42))?;
+        assert_eq!(
+            sample,
+            searcher.search(&AllQuery, &SampleCollector::with_size(10, 42))?
+        );
+        assert_ne!(
+            sample,
+            searcher.search(&AllQuery, &SampleCollector::with_size(10, 43))?
+        );
+        Ok(())
+    }
+}
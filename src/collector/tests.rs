@@ -8,7 +8,7 @@ use crate::DocId;
 use crate::Score;
 use crate::SegmentLocalId;
 
-use crate::collector::{FilterCollector, TopDocs};
+use crate::collector::{Count, FilterCollector, TopDocs};
 use crate::query::QueryParser;
 use crate::schema::{Schema, FAST, TEXT};
 use crate::DateTime;
@@ -71,6 +71,28 @@ pub fn test_filter_collector() {
     assert_eq!(filtered_date_docs.len(), 2);
 }
 
+#[test]
+pub fn test_tuple_collector_single_pass() -> crate::Result<()> {
+    let mut schema_builder = Schema::builder();
+    let text = schema_builder.add_text_field("text", TEXT);
+    let index = Index::create_in_ram(schema_builder.build());
+    let mut index_writer = index.writer_for_tests()?;
+    index_writer.add_document(doc!(text => "a b"));
+    index_writer.add_document(doc!(text => "a a a"));
+    index_writer.add_document(doc!(text => "b"));
+    index_writer.commit()?;
+    index_writer.add_document(doc!(text => "a c"));
+    index_writer.add_document(doc!(text => "a a"));
+    index_writer.commit()?;
+    let searcher = index.reader()?.searcher();
+    let query = QueryParser::for_index(&index, vec![text]).parse_query("a")?;
+    let (count, top_docs) = searcher.search(&query, &(Count, TopDocs::with_limit(2)))?;
+    assert_eq!(count, searcher.search(&query, &Count)?);
+    assert_eq!(top_docs, searcher.search(&query, &TopDocs::with_limit(2))?);
+    assert_eq!(count, 4);
+    Ok(())
+}
+
 /// Stores all of the doc ids.
 /// This collector is only used for tests.
 /// It is unusable in pr
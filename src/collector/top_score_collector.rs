@@ -78,6 +78,47 @@ where
     }
 }
 
+/// Wraps a collector ranking documents by the bitwise complement of a
+/// u64 fast field, and restores the original values on merge.
+///
+/// Complementing the values reverses their order, so that the top-K
+/// collected are the docs with the lowest values.
+struct AscendingFieldCollector<TCollector: Collector<Fruit = Vec<(u64, DocAddress)>>> {
+    collector: TCollector,
+}
+
+impl<TCollector> Collector for AscendingFieldCollector<TCollector>
+where
+    TCollector: Collector<Fruit = Vec<(u64, DocAddress)>>,
+{
+    type Fruit = Vec<(u64, DocAddress)>;
+
+    type Child = TCollector::Child;
+
+    fn for_segment(
+        &self,
+        segment_local_id: crate::SegmentLocalId,
+        segment: &SegmentReader,
+    ) -> crate::Result<Self::Child> {
+        self.collector.for_segment(segment_local_id, segment)
+    }
+
+    fn requires_scoring(&self) -> bool {
+        self.collector.requires_scoring()
+    }
+
+    fn merge_fruits(
+        &self,
+        segment_fruits: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> crate::Result<Self::Fruit> {
+        let raw_result = self.collector.merge_fruits(segment_fruits)?;
+        Ok(raw_result
+            .into_iter()
+            .map(|(complement, doc_address)| (!complement, doc_address))
+            .collect())
+    }
+}
+
 /// The `TopDocs` collector keeps track of the top `K` documents
 /// sorted by their score.
 ///
@@ -130,16 +171,23 @@ impl fmt::Debug for TopDocs {
 
 struct ScorerByFastFieldReader {
     ff_reader: FastFieldReader<u64>,
+    ascending: bool,
 }
 
 impl CustomSegmentScorer<u64> for ScorerByFastFieldReader {
     fn score(&mut self, doc: DocId) -> u64 {
-        self.ff_reader.get(doc)
+        let val = self.ff_reader.get(doc);
+        if self.ascending {
+            !val
+        } else {
+            val
+        }
     }
 }
 
 struct ScorerByField {
     field: Field,
+    ascending: bool,
 }
 
 impl CustomScorer<u64> for ScorerByField {
@@ -154,7 +202,10 @@ impl CustomScorer<u64> for ScorerByField {
         let ff_reader: FastFieldReader<u64> = segment_reader
             .fast_fields()
             .typed_fast_field_reader(self.field)?;
-        Ok(ScorerByFastFieldReader { ff_reader })
+        Ok(ScorerByFastFieldReader {
+            ff_reader,
+            ascending: self.ascending,
+        })
     }
 }
 
@@ -284,7 +335,33 @@ impl TopDocs {
         self,
         field: Field,
     ) -> impl Collector<Fruit = Vec<(u64, DocAddress)>> {
-        CustomScoreTopCollector::new(ScorerByField { field }, self.0.into_tscore())
+        let scorer_by_field = ScorerByField {
+            field,
+            ascending: false,
+        };
+        CustomScoreTopCollector::new(scorer_by_field, self.0.into_tscore())
+    }
+
+    /// Set top-K to rank documents by a given fast field, in ascending order.
+    ///
+    /// This behaves like [`.order_by_u64_field(...)`](#method.order_by_u64_field),
+    /// except that the documents with the lowest values are collected, and
+    /// the resulting vec is sorted increasingly by the field value.
+    /// Ties are broken by ascending `DocAddress`.
+    ///
+    /// As for `.order_by_u64_field(...)`, an error will be returned at the
+    /// moment of search if the field is not a fast field.
+    pub fn order_by_u64_field_asc(
+        self,
+        field: Field,
+    ) -> impl Collector<Fruit = Vec<(u64, DocAddress)>> {
+        let scorer_by_field = ScorerByField {
+            field,
+            ascending: true,
+        };
+        AscendingFieldCollector {
+            collector: CustomScoreTopCollector::new(scorer_by_field, self.0.into_tscore()),
+        }
     }
 
     /// Set top-K to rank documents by a given fast field.
@@ -872,6 +949,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_top_field_collector_ascending() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let timestamp = schema_builder.add_u64_field("timestamp", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for &val in &[30u64, 10, 20, 10, 0, 40] {
+            index_writer.add_document(doc!(timestamp => val));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let top_docs: Vec<(u64, DocAddress)> = searcher.search(
+            &AllQuery,
+            &TopDocs::with_limit(4).order_by_u64_field_asc(timestamp),
+        )?;
+        assert_eq!(
+            &top_docs[..],
+            &[
+                (0, DocAddress(0, 4)),
+                (10, DocAddress(0, 1)),
+                (10, DocAddress(0, 3)),
+                (20, DocAddress(0, 2)),
+            ]
+        );
+        let top_docs: Vec<(u64, DocAddress)> = searcher.search(
+            &AllQuery,
+            &TopDocs::with_limit(3).order_by_u64_field(timestamp),
+        )?;
+        assert_eq!(
+            &top_docs[..],
+            &[
+                (40, DocAddress(0, 5)),
+                (30, DocAddress(0, 0)),
+                (20, DocAddress(0, 2)),
+            ]
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_top_field_collector_datetime() -> crate::Result<()> {
         use std::str::FromStr;
@@ -1061,6 +1177,100 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tweak_score_replaces_ranking() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "tax"));
+        index_writer.add_document(doc!(text => "tax payer with a long description"));
+        index_writer.add_document(doc!(text => "no match"));
+        index_writer.add_document(doc!(text => "tax payer"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = QueryParser::for_index(&index, vec![text]).parse_query("tax")?;
+        let bm25_docs: Vec<DocAddress> = searcher
+            .search(&query, &TopDocs::with_limit(3))?
+            .into_iter()
+            .map(|(_, doc_address)| doc_address)
+            .collect();
+        assert_eq!(
+            bm25_docs,
+            vec![DocAddress(0, 0), DocAddress(0, 3), DocAddress(0, 1)]
+        );
+        // Ranks the longest documents first, ignoring the BM25 score.
+        let by_length =
+            TopDocs::with_limit(3).tweak_score(move |segment_reader: &SegmentReader| {
+                let fieldnorm_reader = segment_reader.get_fieldnorms_reader(text).unwrap();
+                move |doc: DocId, _original_score: Score| fieldnorm_reader.fieldnorm(doc) as Score
+            });
+        assert_eq!(
+            searcher.search(&query, &by_length)?,
+            vec![
+                (6.0, DocAddress(0, 1)),
+                (2.0, DocAddress(0, 3)),
+                (1.0, DocAddress(0, 0))
+            ]
+        );
+        // A constant score keeps the matching docs, in doc id order.
+        let constant =
+            TopDocs::with_limit(3).tweak_score(move |_segment_reader: &SegmentReader| {
+                move |_doc: DocId, _original_score: Score| 1.0 as Score
+            });
+        assert_eq!(
+            searcher.search(&query, &constant)?,
+            vec![
+                (1.0, DocAddress(0, 0)),
+                (1.0, DocAddress(0, 1)),
+                (1.0, DocAddress(0, 3))
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_top_collector_keeps_best_docs_across_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let rank_field = schema_builder.add_u64_field("rank", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let rank = |i: u64| (i * 7_919) % 1_000;
+        for i in 0..1_000 {
+            index_writer.add_document(doc!(rank_field=>rank(i)));
+            if i == 499 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let collector =
+            TopDocs::with_limit(10).custom_score(move |segment_reader: &SegmentReader| {
+                let rank_reader = segment_reader.fast_fields().u64(rank_field).unwrap();
+                move |doc: DocId| rank_reader.get(doc)
+            });
+        let top_docs: Vec<(u64, DocAddress)> = searcher.search(&AllQuery, &collector)?;
+        let mut expected: Vec<(u64, DocAddress)> = searcher
+            .segment_readers()
+            .iter()
+            .enumerate()
+            .flat_map(|(segment_ord, segment_reader)| {
+                let rank_reader = segment_reader.fast_fields().u64(rank_field).unwrap();
+                (0..segment_reader.max_doc())
+                    .map(move |doc| (rank_reader.get(doc), DocAddress(segment_ord as u32, doc)))
+            })
+            .collect();
+        expected.sort_by(|left, right| right.cmp(left));
+        expected.truncate(10);
+        assert_eq!(
+            top_docs.iter().map(|(rank, _)| *rank).collect::<Vec<u64>>(),
+            (990..1_000).rev().collect::<Vec<u64>>()
+        );
+        assert_eq!(top_docs, expected);
+        Ok(())
+    }
+
     fn index(
         query: &str,
         query_field: Field,
@@ -150,12 +150,21 @@ impl BinarySerializable for String {
     }
 
     fn deserialize<R: Read>(reader: &mut R) -> io::Result<String> {
-        let string_length = VInt::deserialize(reader)?.val() as usize;
-        let mut result = String::with_capacity(string_length);
-        reader
-            .take(string_length as u64)
-            .read_to_string(&mut result)?;
-        Ok(result)
+        let string_length = VInt::deserialize(reader)?.val();
+        // The length is not trusted: the buffer only grows as bytes are actually read.
+        let mut data = Vec::new();
+        reader.take(string_length).read_to_end(&mut data)?;
+        if data.len() as u64 != string_length {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "Expected a string of {} bytes, only found {} bytes",
+                    string_length,
+                    data.len()
+                ),
+            ));
+        }
+        String::from_utf8(data).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
     }
 }
 
@@ -216,10 +225,33 @@ pub mod test {
         assert_eq!(serialize_test(String::from("富士さん見える。")), 1 + 3 * 8);
     }
 
+    #[test]
+    fn test_deserialize_string_invalid() {
+        let mut buffer = Vec::new();
+        VInt(2).serialize(&mut buffer).unwrap();
+        buffer.extend_from_slice(&[0xC3, 0x28]);
+        let err = String::deserialize(&mut &buffer[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut truncated = Vec::new();
+        String::from("tantivy").serialize(&mut truncated).unwrap();
+        truncated.pop();
+        let err = String::deserialize(&mut &truncated[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        let mut corrupted_length = Vec::new();
+        VInt(u64::MAX).serialize(&mut corrupted_length).unwrap();
+        corrupted_length.extend_from_slice(b"tantivy");
+        let err = String::deserialize(&mut &corrupted_length[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
     #[test]
     fn test_serialize_vec() {
         assert_eq!(serialize_test(Vec::<u8>::new()), 1);
         assert_eq!(serialize_test(vec![1u32, 3u32]), 1 + 4 * 2);
+        assert_eq!(
+            serialize_test(vec![String::new(), String::from("富士")]),
+            1 + 1 + 1 + 3 * 2
+        );
     }
 
     #[test]
@@ -155,11 +155,30 @@ impl BinarySerializable for VInt {
         loop {
             match bytes.next() {
                 Some(Ok(b)) => {
-                    result |= u64::from(b % 128u8) << shift;
+                    let payload = b % 128u8;
+                    if shift == 63 && payload > 1 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "VInt overflows a u64",
+                        ));
+                    }
+                    result |= u64::from(payload) << shift;
                     if b >= STOP_BIT {
+                        if payload == 0 && shift > 0 {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "Overlong VInt encoding",
+                            ));
+                        }
                         return Ok(VInt(result));
                     }
                     shift += 7;
+                    if shift > 63 {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "VInt is longer than 10 bytes",
+                        ));
+                    }
                 }
                 _ => {
                     return Err(io::Error::new(
@@ -211,6 +230,24 @@ mod tests {
         aux_test_vint(10);
     }
 
+    #[test]
+    fn test_vint_rejects_invalid_encodings() {
+        let deserialize = |bytes: &[u8]| VInt::deserialize(&mut &bytes[..]);
+        // 0 encoded on two bytes.
+        assert!(deserialize(&[0u8, 128u8]).is_err());
+        // 1 encoded on two bytes.
+        assert!(deserialize(&[1u8, 128u8]).is_err());
+        // More than 10 bytes.
+        assert!(deserialize(&[127u8; 11]).is_err());
+        // The 10th byte carries bits beyond the 64th.
+        let mut overflow = [127u8; 10];
+        overflow[9] = 2u8 | 128u8;
+        assert!(deserialize(&overflow).is_err());
+        overflow[9] = 1u8 | 128u8;
+        assert_eq!(deserialize(&overflow).unwrap().val(), u64::max_value());
+        assert!(deserialize(&[127u8]).is_err());
+    }
+
     fn aux_test_serialize_vint_u32(val: u32) {
         let mut buffer = [0u8; 10];
         let mut buffer2 = [0u8; 8];
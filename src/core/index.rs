@@ -22,9 +22,10 @@ use crate::reader::IndexReaderBuilder;
 use crate::schema::Field;
 use crate::schema::FieldType;
 use crate::schema::Schema;
-use crate::tokenizer::{TextAnalyzer, TokenizerManager};
+use crate::tokenizer::{AnalyzerConfig, TextAnalyzer, TokenizerManager};
 use crate::IndexWriter;
-use std::collections::HashSet;
+use crate::INDEX_FORMAT_VERSION;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 #[cfg(feature = "mmap")]
@@ -44,6 +45,16 @@ fn load_metas(
             "Meta file does not contain valid utf8 file.".to_string(),
         )
     })?;
+    // The version is checked first, as the meta of a newer index format
+    // may not be parseable at all.
+    if let Ok(index_format_version) = IndexMeta::deserialize_index_format_version(&meta_string) {
+        if index_format_version > INDEX_FORMAT_VERSION {
+            return Err(TantivyError::UnsupportedVersion {
+                found: index_format_version,
+                supported: INDEX_FORMAT_VERSION,
+            });
+        }
+    }
     IndexMeta::deserialize(&meta_string, &inventory)
         .map_err(|e| {
             DataCorruption::new(
@@ -57,6 +68,52 @@ fn load_metas(
         .map_err(From::from)
 }
 
+/// Returns the default `TokenizerManager`, with the analyzers configured
+/// in the schema registered on top of it.
+///
+/// The analyzers are resolved against the returned `TokenizerManager` when they
+/// are looked up, so that they may use tokenizers registered later on.
+fn tokenizers_for_schema(schema: &Schema) -> crate::Result<TokenizerManager> {
+    let tokenizers = TokenizerManager::default();
+    let mut analyzer_configs: HashMap<&str, &AnalyzerConfig> = HashMap::new();
+    for field_entry in schema.fields().map(|(_, field_entry)| field_entry) {
+        if let FieldType::Str(text_options) = field_entry.field_type() {
+            let indexing_options = match text_options.get_indexing_options() {
+                Some(indexing_options) => indexing_options,
+                None => continue,
+            };
+            let analyzer_config = match indexing_options.analyzer() {
+                Some(analyzer_config) => analyzer_config,
+                None => continue,
+            };
+            let tokenizer_name = indexing_options.tokenizer();
+            if tokenizers.get(tokenizer_name).is_some() {
+                return Err(TantivyError::SchemaError(format!(
+                    "The analyzer of field {:?} cannot be named {:?}, \
+                     the name of a built-in tokenizer.",
+                    field_entry.name(),
+                    tokenizer_name
+                )));
+            }
+            match analyzer_configs.insert(tokenizer_name, analyzer_config) {
+                Some(previous_config) if previous_config != analyzer_config => {
+                    return Err(TantivyError::SchemaError(format!(
+                        "The analyzer {:?} of field {:?} conflicts with another \
+                         analyzer of the same name.",
+                        tokenizer_name,
+                        field_entry.name()
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+    for (tokenizer_name, analyzer_config) in analyzer_configs {
+        tokenizers.register_analyzer(tokenizer_name, analyzer_config.clone());
+    }
+    Ok(tokenizers)
+}
+
 /// Search Index
 #[derive(Clone)]
 pub struct Index {
@@ -71,7 +128,7 @@ impl Index {
     /// Examines the directory to see if it contains an index.
     ///
     /// Effectively, it only checks for the presence of the `meta.json` file.
-    pub fn exists<Dir: Directory>(dir: &Dir) -> Result<bool, OpenReadError> {
+    pub fn exists(dir: &dyn Directory) -> Result<bool, OpenReadError> {
         dir.exists(&META_FILEPATH)
     }
 
@@ -125,8 +182,12 @@ impl Index {
     }
 
     /// Opens or creates a new index in the provided directory
-    pub fn open_or_create<Dir: Directory>(dir: Dir, schema: Schema) -> crate::Result<Index> {
-        if !Index::exists(&dir)? {
+    pub fn open_or_create<T: Into<Box<dyn Directory>>>(
+        dir: T,
+        schema: Schema,
+    ) -> crate::Result<Index> {
+        let dir: Box<dyn Directory> = dir.into();
+        if !Index::exists(dir.as_ref())? {
             return Index::create(dir, schema);
         }
         let index = Index::open(dir)?;
@@ -153,10 +214,11 @@ impl Index {
         Index::create(mmap_directory, schema)
     }
 
-    /// Creates a new index given an implementation of the trait `Directory`.
+    /// Creates a new index given an implementation of the trait `Directory`,
+    /// or a `Box<dyn Directory>`.
     ///
     /// If a directory previously existed, it will be erased.
-    pub fn create<Dir: Directory>(dir: Dir, schema: Schema) -> crate::Result<Index> {
+    pub fn create<T: Into<Box<dyn Directory>>>(dir: T, schema: Schema) -> crate::Result<Index> {
         let directory = ManagedDirectory::wrap(dir)?;
         Index::from_directory(directory, schema)
     }
@@ -165,9 +227,11 @@ impl Index {
     ///
     /// This will overwrite existing meta.json
     fn from_directory(directory: ManagedDirectory, schema: Schema) -> crate::Result<Index> {
-        save_new_metas(schema.clone(), &directory)?;
-        let metas = IndexMeta::with_schema(schema);
-        Index::create_from_metas(directory, &metas, SegmentMetaInventory::default())
+        let metas = IndexMeta::with_schema(schema.clone());
+        let index =
+            Index::create_from_metas(directory.clone(), &metas, SegmentMetaInventory::default())?;
+        save_new_metas(schema, &directory)?;
+        Ok(index)
     }
 
     /// Creates a new index given a directory and an `IndexMeta`.
@@ -177,10 +241,11 @@ impl Index {
         inventory: SegmentMetaInventory,
     ) -> crate::Result<Index> {
         let schema = metas.schema.clone();
+        let tokenizers = tokenizers_for_schema(&schema)?;
         Ok(Index {
             directory,
             schema,
-            tokenizers: TokenizerManager::default(),
+            tokenizers,
             executor: Arc::new(Executor::single_thread()),
             inventory,
         })
@@ -197,10 +262,13 @@ impl Index {
         let field_type = field_entry.field_type();
         let tokenizer_manager: &TokenizerManager = self.tokenizers();
         let tokenizer_name_opt: Option<TextAnalyzer> = match field_type {
-            FieldType::Str(text_options) => text_options
-                .get_indexing_options()
-                .map(|text_indexing_options| text_indexing_options.tokenizer().to_string())
-                .and_then(|tokenizer_name| tokenizer_manager.get(&tokenizer_name)),
+            FieldType::Str(text_options) => match text_options.get_indexing_options() {
+                Some(text_indexing_options) => match text_indexing_options.analyzer() {
+                    Some(analyzer_config) => Some(tokenizer_manager.build(analyzer_config)?),
+                    None => tokenizer_manager.get(text_indexing_options.tokenizer()),
+                },
+                None => None,
+            },
             _ => None,
         };
         match tokenizer_name_opt {
@@ -253,8 +321,10 @@ impl Index {
         self.inventory.new_segment_meta(segment_id, max_doc)
     }
 
-    /// Open the index using the provided directory
-    pub fn open<D: Directory>(directory: D) -> crate::Result<Index> {
+    /// Open the index using the provided directory.
+    ///
+    /// The directory can be given as a concrete type, or as a `Box<dyn Directory>`.
+    pub fn open<T: Into<Box<dyn Directory>>>(directory: T) -> crate::Result<Index> {
         let directory = ManagedDirectory::wrap(directory)?;
         let inventory = SegmentMetaInventory::default();
         let metas = load_metas(&directory, &inventory)?;
@@ -411,12 +481,14 @@ impl fmt::Debug for Index {
 
 #[cfg(test)]
 mod tests {
+    use crate::collector::Count;
+    use crate::core::META_FILEPATH;
     use crate::directory::{RAMDirectory, WatchCallback};
     use crate::schema::Field;
     use crate::schema::{Schema, INDEXED, TEXT};
     use crate::IndexReader;
     use crate::ReloadPolicy;
-    use crate::{Directory, Index};
+    use crate::{Directory, Index, TantivyError, INDEX_FORMAT_VERSION};
 
     #[test]
     fn test_indexer_for_field() {
@@ -440,6 +512,26 @@ mod tests {
         assert!(Index::exists(&directory).unwrap());
     }
 
+    #[test]
+    fn test_index_over_directory_trait_object() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let directory: Box<dyn Directory> = Box::new(RAMDirectory::create());
+        assert!(!Index::exists(directory.as_ref())?);
+        {
+            let index = Index::create(directory.box_clone(), schema)?;
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text=>"hello"));
+            index_writer.add_document(doc!(text=>"happy tax payer"));
+            index_writer.commit()?;
+        }
+        assert!(Index::exists(directory.as_ref())?);
+        let searcher = Index::open(directory)?.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        Ok(())
+    }
+
     #[test]
     fn open_or_create_should_create() {
         let directory = RAMDirectory::create();
@@ -477,6 +569,135 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_open_index_with_future_format_version() -> crate::Result<()> {
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), throw_away_schema())?;
+        assert_eq!(
+            index.load_metas()?.index_format_version,
+            INDEX_FORMAT_VERSION
+        );
+        let meta_json = String::from_utf8(directory.atomic_read(&META_FILEPATH)?).unwrap();
+        let current_version = format!("\"index_format_version\":{}", INDEX_FORMAT_VERSION);
+        assert!(meta_json.contains(&current_version));
+        // The rest of the meta of a future index may not even be parseable.
+        let future_meta_json = meta_json
+            .replace(
+                &current_version,
+                &format!("\"index_format_version\":{}", INDEX_FORMAT_VERSION + 1),
+            )
+            .replace("\"segments\":[]", "\"segments\":{\"future\":true}");
+        directory.atomic_write(&META_FILEPATH, future_meta_json.as_bytes())?;
+        assert!(matches!(
+            Index::open(directory),
+            Err(TantivyError::UnsupportedVersion { found, supported })
+                if found == INDEX_FORMAT_VERSION + 1 && supported == INDEX_FORMAT_VERSION
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyzer_config_persisted_with_schema() -> crate::Result<()> {
+        use crate::query::QueryParser;
+        use crate::schema::{IndexRecordOption, TextFieldIndexing, TextOptions};
+        use crate::tokenizer::{AnalyzerConfig, Language, TokenFilterConfig};
+        let analyzer_config = AnalyzerConfig::new("default")
+            .filter(TokenFilterConfig::StopWords(vec!["the".to_string()]))
+            .filter(TokenFilterConfig::Stemmer(Language::English));
+        let text_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default()
+                .set_analyzer("title_stem", analyzer_config.clone())
+                .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+        );
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", text_options);
+        let schema = schema_builder.build();
+        let directory = RAMDirectory::create();
+        {
+            let index = Index::create(directory.clone(), schema.clone())?;
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(title => "The Running Dogs"));
+            index_writer.commit()?;
+        }
+        // A fresh process only has the default tokenizers registered.
+        let index = Index::open(directory)?;
+        assert_eq!(index.schema(), schema);
+        assert!(index.tokenizers().get("title_stem").is_some());
+        let query_parser = QueryParser::for_index(&index, vec![title]);
+        let searcher = index.reader()?.searcher();
+        for query_str in &["runs", "DOG", "\"the running dog\""] {
+            let query = query_parser.parse_query(query_str)?;
+            assert_eq!(searcher.search(&query, &Count)?, 1, "{}", query_str);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyzer_config_custom_tokenizer() -> crate::Result<()> {
+        use crate::query::QueryParser;
+        use crate::schema::{TextFieldIndexing, TextOptions};
+        use crate::tokenizer::{AnalyzerConfig, RawTokenizer, TokenFilterConfig};
+        let analyzer_config = AnalyzerConfig::new("klingon").filter(TokenFilterConfig::LowerCaser);
+        let text_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default().set_analyzer("custom", analyzer_config),
+        );
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        assert!(index.tokenizers().get("custom").is_none());
+        assert!(matches!(
+            index.tokenizer_for_field(title),
+            Err(TantivyError::SchemaError(msg)) if msg.contains("\"klingon\"")
+        ));
+        // The analyzer is resolved against the tokenizers of the index.
+        index.tokenizers().register("klingon", RawTokenizer);
+        assert!(index.tokenizer_for_field(title).is_ok());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title => "Qapla Batlh"));
+        index_writer.commit()?;
+        let query = QueryParser::for_index(&index, vec![title]).parse_query("\"qapla batlh\"")?;
+        assert_eq!(index.reader()?.searcher().search(&query, &Count)?, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyzer_config_name_conflicts() {
+        use crate::schema::{TextFieldIndexing, TextOptions};
+        use crate::tokenizer::{AnalyzerConfig, Language, TokenFilterConfig};
+        let analyzer_options = |tokenizer_name: &str, analyzer_config: AnalyzerConfig| {
+            TextOptions::default().set_indexing_options(
+                TextFieldIndexing::default().set_analyzer(tokenizer_name, analyzer_config),
+            )
+        };
+        let stemmed =
+            AnalyzerConfig::new("default").filter(TokenFilterConfig::Stemmer(Language::English));
+        let create_index = |fields: Vec<(&'static str, TextOptions)>| {
+            let mut schema_builder = Schema::builder();
+            for (field_name, text_options) in fields {
+                schema_builder.add_text_field(field_name, text_options);
+            }
+            Index::create(RAMDirectory::create(), schema_builder.build())
+        };
+        // Fields may share an analyzer, as long as they configure it the same way.
+        assert!(create_index(vec![
+            ("title", analyzer_options("stemmed", stemmed.clone())),
+            ("body", analyzer_options("stemmed", stemmed.clone())),
+        ])
+        .is_ok());
+        assert!(matches!(
+            create_index(vec![
+                ("title", analyzer_options("stemmed", stemmed.clone())),
+                ("body", analyzer_options("stemmed", AnalyzerConfig::new("default"))),
+            ]),
+            Err(TantivyError::SchemaError(msg)) if msg.contains("\"stemmed\"")
+        ));
+        // The built-in tokenizers cannot be replaced.
+        assert!(matches!(
+            create_index(vec![("title", analyzer_options("default", stemmed))]),
+            Err(TantivyError::SchemaError(msg)) if msg.contains("\"default\"")
+        ));
+    }
+
     fn throw_away_schema() -> Schema {
         let mut schema_builder = Schema::builder();
         let _ = schema_builder.add_u64_field("num_likes", INDEXED);
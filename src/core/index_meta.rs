@@ -1,8 +1,12 @@
 use super::SegmentComponent;
 use crate::core::SegmentId;
+use crate::postings::POSTINGS_FORMAT_VERSION;
 use crate::schema::Schema;
+use crate::DateTime;
 use crate::Opstamp;
+use crate::INDEX_FORMAT_VERSION;
 use census::{Inventory, TrackedObject};
+use chrono::{TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fmt;
@@ -34,6 +38,9 @@ impl SegmentMetaInventory {
             segment_id,
             max_doc,
             deletes: None,
+            postings_format_version: POSTINGS_FORMAT_VERSION,
+            created_at: None,
+            merged_from: Vec::new(),
         };
         SegmentMeta::from(self.inventory.track(inner))
     }
@@ -89,6 +96,34 @@ impl SegmentMeta {
             .unwrap_or(0u32)
     }
 
+    /// Returns the version of the postings format
+    /// the segment was written with.
+    pub fn postings_format_version(&self) -> u32 {
+        self.tracked.postings_format_version
+    }
+
+    /// Returns the time at which the segment was created, that is
+    /// the time of the commit that published it.
+    ///
+    /// A segment produced by a merge keeps the most recent creation time
+    /// of the segments it was merged from, so that none of its documents
+    /// appear older than they are.
+    ///
+    /// Returns `None` for segments that are not committed yet,
+    /// and for segments written by a version of tantivy that did not record it.
+    pub fn created_at(&self) -> Option<DateTime> {
+        self.tracked
+            .created_at
+            .map(|timestamp| Utc.timestamp(timestamp, 0))
+    }
+
+    /// Returns the ids of the segments this segment was merged from.
+    ///
+    /// It is empty if this segment was not produced by a merge.
+    pub fn merged_from(&self) -> &[SegmentId] {
+        &self.tracked.merged_from
+    }
+
     /// Returns the list of files that
     /// are required for the segment meta.
     ///
@@ -157,9 +192,36 @@ impl SegmentMeta {
         assert_eq!(self.tracked.max_doc, 0);
         assert!(self.tracked.deletes.is_none());
         let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
-            segment_id: inner_meta.segment_id,
             max_doc,
             deletes: None,
+            ..inner_meta.clone()
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Sets the time at which the segment was created.
+    pub(crate) fn with_created_at(self, created_at: DateTime) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            created_at: Some(created_at.timestamp()),
+            ..inner_meta.clone()
+        });
+        SegmentMeta { tracked }
+    }
+
+    /// Records the ids of the segments this segment was merged from.
+    pub(crate) fn with_merged_from(self, merged_from: Vec<SegmentId>) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            merged_from,
+            ..inner_meta.clone()
+        });
+        SegmentMeta { tracked }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn with_postings_format_version(self, postings_format_version: u32) -> SegmentMeta {
+        let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
+            postings_format_version,
+            ..inner_meta.clone()
         });
         SegmentMeta { tracked }
     }
@@ -171,9 +233,8 @@ impl SegmentMeta {
             opstamp,
         };
         let tracked = self.tracked.map(move |inner_meta| InnerSegmentMeta {
-            segment_id: inner_meta.segment_id,
-            max_doc: inner_meta.max_doc,
             deletes: Some(delete_meta),
+            ..inner_meta.clone()
         });
         SegmentMeta { tracked }
     }
@@ -184,6 +245,19 @@ struct InnerSegmentMeta {
     segment_id: SegmentId,
     max_doc: u32,
     deletes: Option<DeleteMeta>,
+    #[serde(default = "legacy_postings_format_version")]
+    postings_format_version: u32,
+    // UTC timestamp, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    merged_from: Vec<SegmentId>,
+}
+
+// Segment metas written before the postings format version was recorded
+// all use the version 2 of the postings format.
+fn legacy_postings_format_version() -> u32 {
+    2
 }
 
 impl InnerSegmentMeta {
@@ -210,6 +284,10 @@ pub struct IndexMeta {
     pub schema: Schema,
     /// Opstamp associated to the last `commit` operation.
     pub opstamp: Opstamp,
+    // Version of the on-disk format the index was written with.
+    // Always `INDEX_FORMAT_VERSION` for the metas written by this version of tantivy.
+    #[serde(default = "legacy_index_format_version")]
+    pub(crate) index_format_version: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Payload associated to the last commit.
     ///
@@ -224,6 +302,8 @@ struct UntrackedIndexMeta {
     pub segments: Vec<InnerSegmentMeta>,
     pub schema: Schema,
     pub opstamp: Opstamp,
+    #[serde(default = "legacy_index_format_version")]
+    pub index_format_version: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload: Option<String>,
 }
@@ -238,11 +318,24 @@ impl UntrackedIndexMeta {
                 .collect::<Vec<SegmentMeta>>(),
             schema: self.schema,
             opstamp: self.opstamp,
+            index_format_version: self.index_format_version,
             payload: self.payload,
         }
     }
 }
 
+// Index metas written before the index format version was recorded
+// all use the version 3 of the index format.
+fn legacy_index_format_version() -> u32 {
+    3
+}
+
+#[derive(Deserialize)]
+struct IndexFormatVersion {
+    #[serde(default = "legacy_index_format_version")]
+    index_format_version: u32,
+}
+
 impl IndexMeta {
     /// Create an `IndexMeta` object representing a brand new `Index`
     /// with the given index.
@@ -254,10 +347,21 @@ impl IndexMeta {
             segments: vec![],
             schema,
             opstamp: 0u64,
+            index_format_version: INDEX_FORMAT_VERSION,
             payload: None,
         }
     }
 
+    /// Reads the index format version of a serialized `IndexMeta`,
+    /// without deserializing the rest of the meta.
+    ///
+    /// This makes it possible to reject an index written by a newer version
+    /// of tantivy before attempting to parse it.
+    pub(crate) fn deserialize_index_format_version(meta_json: &str) -> serde_json::Result<u32> {
+        let format_version: IndexFormatVersion = serde_json::from_str(meta_json)?;
+        Ok(format_version.index_format_version)
+    }
+
     pub(crate) fn deserialize(
         meta_json: &str,
         inventory: &SegmentMetaInventory,
@@ -281,7 +385,8 @@ impl fmt::Debug for IndexMeta {
 #[cfg(test)]
 mod tests {
 
-    use super::IndexMeta;
+    use super::{IndexMeta, SegmentMetaInventory};
+    use crate::postings::POSTINGS_FORMAT_VERSION;
     use crate::schema::{Schema, TEXT};
     use serde_json;
 
@@ -296,12 +401,24 @@ mod tests {
             segments: Vec::new(),
             schema,
             opstamp: 0u64,
+            index_format_version: 3,
             payload: None,
         };
         let json = serde_json::ser::to_string(&index_metas).expect("serialization failed");
         assert_eq!(
             json,
-            r#"{"segments":[],"schema":[{"name":"text","type":"text","options":{"indexing":{"record":"position","tokenizer":"default"},"stored":false}}],"opstamp":0}"#
+            r#"{"segments":[],"schema":[{"name":"text","type":"text","options":{"indexing":{"record":"position","tokenizer":"default"},"stored":false}}],"opstamp":0,"index_format_version":3}"#
         );
     }
+
+    #[test]
+    fn test_deserialize_segment_meta_without_postings_format_version() {
+        let meta_json = r#"{"segments":[{"segment_id":"2b5a4b1a-4b33-4b1b-9b6a-2d1f6d5b0b7c","max_doc":3,"deletes":null}],"schema":[{"name":"text","type":"text","options":{"indexing":{"record":"position","tokenizer":"default"},"stored":false}}],"opstamp":0}"#;
+        let inventory = SegmentMetaInventory::default();
+        let index_meta = IndexMeta::deserialize(meta_json, &inventory).unwrap();
+        assert_eq!(index_meta.segments[0].postings_format_version(), 2);
+        assert_eq!(index_meta.segments[0].created_at(), None);
+        assert!(index_meta.segments[0].merged_from().is_empty());
+        assert_eq!(index_meta.index_format_version, 3);
+    }
 }
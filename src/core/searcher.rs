@@ -3,15 +3,19 @@ use tantivy_fst::Ulen;
 use crate::collector::Collector;
 use crate::core::Executor;
 
+use crate::core::SegmentId;
 use crate::core::SegmentReader;
-use crate::query::Query;
+use crate::query::{Query, QueryCost};
 use crate::schema::Document;
+use crate::schema::Field;
 use crate::schema::Schema;
 use crate::schema::Term;
 use crate::space_usage::SearcherSpaceUsage;
 use crate::store::StoreReader;
 use crate::DocAddress;
 use crate::Index;
+use crate::Score;
+use crate::TantivyError;
 
 use std::{fmt, io};
 
@@ -55,9 +59,21 @@ impl Searcher {
     ///
     /// The searcher uses the segment ordinal to route the
     /// the request to the right `Segment`.
+    ///
+    /// Returns an `InvalidArgument` error if the segment ordinal or the
+    /// doc id do not belong to this searcher, as may happen with a
+    /// `DocAddress` obtained from another searcher.
     pub fn doc(&self, doc_address: DocAddress) -> crate::Result<Document> {
         let DocAddress(segment_local_id, doc_id) = doc_address;
-        let store_reader = &self.store_readers[segment_local_id as usize];
+        let store_reader = self
+            .store_readers
+            .get(segment_local_id as usize)
+            .ok_or_else(|| {
+                TantivyError::InvalidArgument(format!(
+                    "Unknown segment ordinal {} in {:?}.",
+                    segment_local_id, doc_address
+                ))
+            })?;
         let doc = store_reader.get(doc_id)?;
         crate::info_log(format!("read content of doc {:?}", doc.field_values()));
         Ok(doc)
@@ -97,6 +113,67 @@ impl Searcher {
         Ok(total_doc_freq)
     }
 
+    /// Return the overall number of alive documents containing
+    /// both `term_a` and `term_b`.
+    ///
+    /// See [`SegmentReader::intersection_count`](./struct.SegmentReader.html#method.intersection_count).
+    pub fn intersection_count(&self, term_a: &Term, term_b: &Term) -> crate::Result<u64> {
+        let mut total_count = 0u64;
+        for segment_reader in &self.segment_readers {
+            total_count += u64::from(segment_reader.intersection_count(term_a, term_b)?);
+        }
+        Ok(total_count)
+    }
+
+    /// Returns the overall number of tokens indexed for the given field.
+    ///
+    /// The count is accumulated at indexing time and recorded in each segment
+    /// (including deleted documents).
+    pub fn total_num_tokens(&self, field: Field) -> crate::Result<u64> {
+        let mut total_num_tokens = 0u64;
+        for segment_reader in &self.segment_readers {
+            let inverted_index = segment_reader.inverted_index(field)?;
+            total_num_tokens += inverted_index.total_num_tokens();
+        }
+        Ok(total_num_tokens)
+    }
+
+    /// Returns the average number of tokens per document for the given field
+    /// (`avgdl` in BM25), computed across all of the segments.
+    ///
+    /// Deleted documents are taken in account. Returns 0 if the searcher
+    /// has no documents.
+    pub fn average_fieldnorm(&self, field: Field) -> crate::Result<Score> {
+        let total_num_tokens = self.total_num_tokens(field)?;
+        let total_num_docs: u64 = self
+            .segment_readers
+            .iter()
+            .map(|segment_reader| u64::from(segment_reader.max_doc()))
+            .sum();
+        if total_num_docs == 0 {
+            return Ok(0.0);
+        }
+        Ok(total_num_tokens as Score / total_num_docs as Score)
+    }
+
+    /// Estimates the cost of running the given query, without running it.
+    ///
+    /// The estimate relies on the doc freq statistics of each segment:
+    /// a conjunction is bounded by its rarest clause, a disjunction is the
+    /// sum of its clauses, and automaton based queries (regex, fuzzy) sum the
+    /// doc freqs of the terms they expand to.
+    ///
+    /// This makes it possible to reject or warn about expensive queries
+    /// before executing them.
+    pub fn estimate_cost(&self, query: &dyn Query) -> crate::Result<QueryCost> {
+        let weight = query.weight(self, false)?;
+        let mut cost = QueryCost::default();
+        for segment_reader in &self.segment_readers {
+            cost += weight.estimate_cost(segment_reader)?;
+        }
+        Ok(cost)
+    }
+
     /// Return the list of segment readers
     pub fn segment_readers(&self) -> &[SegmentReader] {
         &self.segment_readers
@@ -160,6 +237,47 @@ impl Searcher {
         collector.merge_fruits(fruits)
     }
 
+    /// Same as [`search(...)`](#method.search) but only evaluates the query
+    /// on the segments with the given ids.
+    ///
+    /// Segment ordinals are those of the searcher, so the `DocAddress`es
+    /// in the result can be passed to [`doc(...)`](#method.doc).
+    ///
+    /// Returns an error if one of the ids is not a segment of this searcher.
+    pub fn search_segments<C: Collector>(
+        &self,
+        query: &dyn Query,
+        segment_ids: &[SegmentId],
+        collector: &C,
+    ) -> crate::Result<C::Fruit> {
+        for segment_id in segment_ids {
+            if !self
+                .segment_readers
+                .iter()
+                .any(|segment_reader| segment_reader.segment_id() == *segment_id)
+            {
+                return Err(TantivyError::InvalidArgument(format!(
+                    "Segment {:?} is not part of the searcher.",
+                    segment_id
+                )));
+            }
+        }
+        let scoring_enabled = collector.requires_scoring();
+        let weight = query.weight(self, scoring_enabled)?;
+        let segment_readers = self
+            .segment_readers
+            .iter()
+            .enumerate()
+            .filter(|(_, segment_reader)| segment_ids.contains(&segment_reader.segment_id()));
+        let fruits = self.index.search_executor().map(
+            |(segment_ord, segment_reader)| {
+                collector.collect_segment(weight.as_ref(), segment_ord as u32, segment_reader)
+            },
+            segment_readers,
+        )?;
+        collector.merge_fruits(fruits)
+    }
+
     /// Summarize total space usage of this searcher.
     pub fn space_usage(&self) -> io::Result<SearcherSpaceUsage> {
         let mut space_usage = SearcherSpaceUsage::new();
@@ -180,3 +298,167 @@ impl fmt::Debug for Searcher {
         write!(f, "Searcher({:?})", segment_ids)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::core::{Executor, SegmentId};
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, STORED, TEXT};
+    use crate::{assert_nearly_equals, DocAddress, Index, TantivyError, Term};
+
+    #[test]
+    fn test_search_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>"b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let query = TermQuery::new(Term::from_field_text(text, "a"), IndexRecordOption::Basic);
+        assert_eq!(searcher.search(&query, &DocSetCollector)?.len(), 3);
+        let mut num_matches_per_segment = Vec::new();
+        for (segment_ord, segment_reader) in searcher.segment_readers().iter().enumerate() {
+            let docs = searcher.search_segments(
+                &query,
+                &[segment_reader.segment_id()],
+                &DocSetCollector,
+            )?;
+            assert!(docs
+                .iter()
+                .all(|&DocAddress(doc_segment_ord, _)| doc_segment_ord == segment_ord as u32));
+            num_matches_per_segment.push(docs.len());
+        }
+        num_matches_per_segment.sort();
+        assert_eq!(num_matches_per_segment, vec![1, 2]);
+        let unknown_segment = SegmentId::generate_random();
+        assert!(matches!(
+            searcher.search_segments(&query, &[unknown_segment], &DocSetCollector),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_with_multithread_executor() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for segment in 0..4 {
+            for doc in 0..50 {
+                let num_a = (segment * 50 + doc) % 7 + 1;
+                index_writer.add_document(doc!(text=>vec!["a"; num_a].join(" ") + " b"));
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 4);
+        let query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        let collector = (TopDocs::with_limit(20), DocSetCollector);
+        let single_thread_fruit =
+            searcher.search_with_executor(&query, &collector, &Executor::single_thread())?;
+        let multi_thread_fruit = searcher.search_with_executor(
+            &query,
+            &collector,
+            &Executor::multi_thread(3, "search-test")?,
+        )?;
+        assert_eq!(single_thread_fruit.1.len(), 200);
+        assert_eq!(multi_thread_fruit, single_thread_fruit);
+        Ok(())
+    }
+
+    #[test]
+    fn test_doc_freq_and_num_docs() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..100 {
+            if i % 2 == 0 {
+                index_writer.add_document(doc!(text=>"common even"));
+            } else {
+                index_writer.add_document(doc!(text=>"common common"));
+            }
+            if i == 59 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.add_document(doc!(text=>"rare"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(searcher.num_docs(), 101);
+        let doc_freq = |text_str: &str| searcher.doc_freq(&Term::from_field_text(text, text_str));
+        assert_eq!(doc_freq("common")?, 100);
+        assert_eq!(doc_freq("even")?, 50);
+        assert_eq!(doc_freq("rare")?, 1);
+        assert_eq!(doc_freq("missing")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_doc() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.add_document(doc!(text=>"b"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let doc = searcher.doc(DocAddress(0, 1))?;
+        assert_eq!(doc.get_first(text).unwrap().text(), Some("b"));
+        assert!(matches!(
+            searcher.doc(DocAddress(1, 0)),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            searcher.doc(DocAddress(0, 2)),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_average_fieldnorm_across_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let other = schema_builder.add_text_field("other", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a b c", other=>"x"));
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text=>"b c d e f"));
+        index_writer.add_document(doc!(other=>"x y"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(searcher.total_num_tokens(text)?, 9);
+        assert_nearly_equals!(searcher.average_fieldnorm(text)?, 9.0 / 4.0);
+        assert_eq!(searcher.total_num_tokens(other)?, 3);
+        assert_nearly_equals!(searcher.average_fieldnorm(other)?, 3.0 / 4.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_average_fieldnorm_empty_index() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.total_num_tokens(text)?, 0);
+        assert_eq!(searcher.average_fieldnorm(text)?, 0.0);
+        Ok(())
+    }
+}
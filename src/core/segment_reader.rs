@@ -8,16 +8,20 @@ use crate::fastfield::DeleteBitSet;
 use crate::fastfield::FacetReader;
 use crate::fastfield::FastFieldReaders;
 use crate::fieldnorm::{FieldNormReader, FieldNormReaders};
+use crate::postings::POSTINGS_FORMAT_VERSION;
+use crate::query::{intersection_count, levenshtein_dfa, DFAWrapper, MAX_LEVENSHTEIN_DISTANCE};
 use crate::schema::FieldType;
 use crate::schema::Schema;
-use crate::schema::{Field, IndexRecordOption};
+use crate::schema::{Field, IndexRecordOption, Term};
 use crate::space_usage::SegmentSpaceUsage;
 use crate::store::StoreReader;
 use crate::termdict::TermDictionary;
-use crate::DocId;
+use crate::{DateTime, DocId, TantivyError};
 use crate::{common::CompositeFile, error::DataCorruption};
 use fail::fail_point;
+use levenshtein_automata::Distance;
 use std::fmt;
+use std::str;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::{collections::HashMap, io};
@@ -42,6 +46,7 @@ pub struct SegmentReader {
     segment_id: SegmentId,
     max_doc: DocId,
     num_docs: DocId,
+    created_at: Option<DateTime>,
 
     termdict_composite: CompositeFile,
     postings_composite: CompositeFile,
@@ -148,6 +153,14 @@ impl SegmentReader {
 
     /// Open a new segment for reading.
     pub fn open(segment: &Segment) -> crate::Result<SegmentReader> {
+        let postings_format_version = segment.meta().postings_format_version();
+        if postings_format_version != POSTINGS_FORMAT_VERSION {
+            return Err(crate::TantivyError::UnsupportedVersion {
+                found: postings_format_version,
+                supported: POSTINGS_FORMAT_VERSION,
+            });
+        }
+
         let termdict_file = segment.open_read(SegmentComponent::TERMS)?;
         let termdict_composite = CompositeFile::open(&termdict_file)?;
 
@@ -198,6 +211,7 @@ impl SegmentReader {
             inv_idx_reader_cache: Default::default(),
             max_doc: segment.meta().max_doc(),
             num_docs: segment.meta().num_docs(),
+            created_at: segment.meta().created_at(),
             termdict_composite,
             postings_composite,
             fast_fields_readers: fast_field_readers,
@@ -292,6 +306,11 @@ impl SegmentReader {
         self.segment_id
     }
 
+    /// Returns the time at which the segment was created, if it was recorded.
+    pub fn created_at(&self) -> Option<DateTime> {
+        self.created_at
+    }
+
     /// Returns the bitset representing
     /// the documents that have been deleted.
     pub fn delete_bitset(&self) -> Option<&DeleteBitSet> {
@@ -306,6 +325,56 @@ impl SegmentReader {
             .unwrap_or(false)
     }
 
+    /// Returns the number of alive documents containing both `term_a` and `term_b`.
+    ///
+    /// The posting lists are intersected by leapfrogging: the rarest term drives
+    /// the iteration and the other posting list is only moved forward with seeks.
+    /// The intersection is never materialized.
+    pub fn intersection_count(&self, term_a: &Term, term_b: &Term) -> crate::Result<u32> {
+        let postings_a = self
+            .inverted_index(term_a.field())?
+            .read_postings(term_a, IndexRecordOption::Basic)?;
+        let postings_b = self
+            .inverted_index(term_b.field())?
+            .read_postings(term_b, IndexRecordOption::Basic)?;
+        match (postings_a, postings_b) {
+            (Some(postings_a), Some(postings_b)) => {
+                Ok(intersection_count(postings_a, postings_b, |doc| {
+                    !self.is_deleted(doc)
+                }))
+            }
+            _ => Ok(0),
+        }
+    }
+
+    /// Returns the terms of `field` within `max_distance` edits of `term`,
+    /// in term order, along with their Levenshtein distance to `term`.
+    ///
+    /// The term dictionary is intersected with a Levenshtein automaton, so that only
+    /// the terms that can match are visited. `max_distance` is capped at 2, as the
+    /// size of the automaton grows exponentially with the distance.
+    pub fn fuzzy_terms(
+        &self,
+        field: Field,
+        term: &[u8],
+        max_distance: u8,
+    ) -> crate::Result<Vec<(Vec<u8>, u8)>> {
+        let text = str::from_utf8(term).map_err(|_| {
+            TantivyError::InvalidArgument(format!("Fuzzy term {:?} is not valid utf-8", term))
+        })?;
+        let distance = max_distance.min(MAX_LEVENSHTEIN_DISTANCE);
+        let automaton = DFAWrapper(levenshtein_dfa(text, distance, false)?);
+        let inverted_index = self.inverted_index(field)?;
+        let mut term_stream = inverted_index.terms().search(&automaton).into_stream()?;
+        let mut fuzzy_terms = Vec::new();
+        while term_stream.advance() {
+            if let Distance::Exact(distance) = automaton.0.eval(term_stream.key()) {
+                fuzzy_terms.push((term_stream.key().to_vec(), distance));
+            }
+        }
+        Ok(fuzzy_terms)
+    }
+
     /// Returns an iterator that will iterate over the alive document ids
     pub fn doc_ids_alive(&self) -> impl Iterator<Item = DocId> + '_ {
         (0u32..self.max_doc).filter(move |doc| !self.is_deleted(*doc))
@@ -338,9 +407,94 @@ impl fmt::Debug for SegmentReader {
 
 #[cfg(test)]
 mod test {
+    use super::SegmentReader;
+    use crate::collector::DocSetCollector;
     use crate::core::Index;
-    use crate::schema::{Schema, Term, STORED, TEXT};
-    use crate::DocId;
+    use crate::postings::POSTINGS_FORMAT_VERSION;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, Term, STORED, STRING, TEXT};
+    use crate::{DocAddress, DocId, TantivyError};
+    use chrono::Utc;
+    use futures::executor::block_on;
+
+    #[test]
+    fn test_intersection_count() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..300u32 {
+            let mut words = vec![format!("n{}", i)];
+            if i % 2 == 0 {
+                words.push("even".to_string());
+            }
+            if i % 3 == 0 {
+                words.push("triple".to_string());
+            }
+            index_writer.add_document(doc!(text => words.join(" ")));
+            if i == 149 {
+                index_writer.commit()?;
+            }
+        }
+        index_writer.delete_term(Term::from_field_text(text, "n6"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        let even = Term::from_field_text(text, "even");
+        let triple = Term::from_field_text(text, "triple");
+        let missing = Term::from_field_text(text, "missing");
+        // 50 multiples of 6 in 0..300, one of them deleted.
+        assert_eq!(searcher.intersection_count(&even, &triple)?, 49);
+        assert_eq!(searcher.intersection_count(&triple, &even)?, 49);
+        assert_eq!(searcher.intersection_count(&even, &missing)?, 0);
+        let per_segment: u32 = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment_reader| segment_reader.intersection_count(&even, &triple))
+            .sum::<crate::Result<u32>>()?;
+        assert_eq!(per_segment, 49);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fuzzy_terms() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "color colar colour colours red"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        // "colour" is a single insertion away from "color", "colours" two.
+        assert_eq!(
+            segment_reader.fuzzy_terms(text, b"color", 1)?,
+            vec![
+                (b"colar".to_vec(), 1),
+                (b"color".to_vec(), 0),
+                (b"colour".to_vec(), 1)
+            ]
+        );
+        assert_eq!(
+            segment_reader.fuzzy_terms(text, b"colr", 1)?,
+            vec![(b"colar".to_vec(), 1), (b"color".to_vec(), 1)]
+        );
+        assert_eq!(
+            segment_reader.fuzzy_terms(text, b"colours", 0)?,
+            vec![(b"colours".to_vec(), 0)]
+        );
+        // The distance is capped at 2.
+        assert_eq!(
+            segment_reader.fuzzy_terms(text, b"color", 5)?,
+            segment_reader.fuzzy_terms(text, b"color", 2)?
+        );
+        assert_eq!(segment_reader.fuzzy_terms(text, b"color", 2)?.len(), 4);
+        assert!(matches!(
+            segment_reader.fuzzy_terms(text, b"\xFF", 1),
+            Err(TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
 
     #[test]
     fn test_alive_docs_iterator() -> crate::Result<()> {
@@ -373,4 +527,147 @@ mod test {
         assert_eq!(vec![0u32, 2u32], docs);
         Ok(())
     }
+
+    #[test]
+    fn test_deleted_doc_is_skipped_by_search() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id => "0", text => "hello"));
+        index_writer.add_document(doc!(id => "1", text => "hello"));
+        index_writer.add_document(doc!(id => "2", text => "hello"));
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(id, "1"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        assert!(segment_reader.has_deletes());
+        assert!(!segment_reader.is_deleted(0));
+        assert!(segment_reader.is_deleted(1));
+        assert!(!segment_reader.is_deleted(2));
+        let query = TermQuery::new(
+            Term::from_field_text(text, "hello"),
+            IndexRecordOption::Basic,
+        );
+        assert_eq!(
+            searcher.search(&query, &DocSetCollector)?,
+            vec![DocAddress(0, 0), DocAddress(0, 2)].into_iter().collect()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_created_at_and_merged_from() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let before = Utc::now().timestamp();
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text=>"b"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        for segment_reader in searcher.segment_readers() {
+            let created_at = segment_reader.created_at().unwrap().timestamp();
+            assert!(before <= created_at && created_at <= Utc::now().timestamp());
+        }
+        let latest_created_at = searcher
+            .segment_readers()
+            .iter()
+            .filter_map(SegmentReader::created_at)
+            .max();
+        let mut segment_ids = index.searchable_segment_ids()?;
+        segment_ids.sort();
+        let merged_segment_meta = block_on(index_writer.merge(&segment_ids))?;
+        let mut merged_from = merged_segment_meta.merged_from().to_vec();
+        merged_from.sort();
+        assert_eq!(merged_from, segment_ids);
+        // A merged segment is as recent as the most recent of its segments.
+        assert_eq!(merged_segment_meta.created_at(), latest_created_at);
+        index_writer.add_document(doc!(text=>"c"));
+        index_writer.commit()?;
+        // The lineage and creation time are persisted in meta.json,
+        // and left untouched by later commits.
+        let segment_metas = index.load_metas()?.segments;
+        assert_eq!(segment_metas.len(), 2);
+        let merged_segment_meta = segment_metas
+            .iter()
+            .find(|segment_meta| segment_meta.id() == merged_segment_meta.id())
+            .unwrap();
+        let mut merged_from = merged_segment_meta.merged_from().to_vec();
+        merged_from.sort();
+        assert_eq!(merged_from, segment_ids);
+        assert_eq!(merged_segment_meta.created_at(), latest_created_at);
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_reader_unsupported_postings_format_version() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        let segment_meta = index
+            .searchable_segment_metas()?
+            .into_iter()
+            .next()
+            .unwrap();
+        assert_eq!(
+            segment_meta.postings_format_version(),
+            POSTINGS_FORMAT_VERSION
+        );
+        let segment = index.segment(segment_meta.clone());
+        assert!(SegmentReader::open(&segment).is_ok());
+        let future_segment = index.segment(
+            segment_meta
+                .clone()
+                .with_postings_format_version(POSTINGS_FORMAT_VERSION + 1),
+        );
+        assert!(matches!(
+            SegmentReader::open(&future_segment),
+            Err(TantivyError::UnsupportedVersion { found, supported })
+                if found == POSTINGS_FORMAT_VERSION + 1 && supported == POSTINGS_FORMAT_VERSION
+        ));
+        // Older versions of the postings format cannot be decoded either.
+        let past_segment =
+            index.segment(segment_meta.with_postings_format_version(POSTINGS_FORMAT_VERSION - 1));
+        assert!(matches!(
+            SegmentReader::open(&past_segment),
+            Err(TantivyError::UnsupportedVersion { found, supported })
+                if found == POSTINGS_FORMAT_VERSION - 1 && supported == POSTINGS_FORMAT_VERSION
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fieldnorms_round_trip() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let stored = schema_builder.add_text_field("stored", STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let lengths = [0usize, 1, 7, 40, 100, 1_000];
+        for &len in &lengths {
+            index_writer.add_document(doc!(text=>vec!["word"; len].join(" ")));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let fieldnorm_reader = segment_reader.get_fieldnorms_reader(text)?;
+        for (doc, &len) in lengths.iter().enumerate() {
+            let fieldnorm = fieldnorm_reader.fieldnorm(doc as DocId);
+            // Norms are quantized on one byte: the decoded value is the
+            // largest bucket lower bound that does not exceed the length.
+            assert!(fieldnorm as usize <= len);
+            assert!(fieldnorm as usize * 9 >= len * 8);
+        }
+        assert!(segment_reader.get_fieldnorms_reader(stored).is_err());
+        Ok(())
+    }
 }
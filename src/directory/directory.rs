@@ -1,3 +1,4 @@
+use crate::core::META_FILEPATH;
 use crate::directory::directory_lock::Lock;
 use crate::directory::error::LockError;
 use crate::directory::error::{DeleteError, OpenReadError, OpenWriteError};
@@ -88,6 +89,17 @@ fn try_acquire_lock(
     })))
 }
 
+/// Returns the files of a batch in the order in which they should be published.
+///
+/// The order of the batch is preserved, except for `meta.json` which is moved last.
+pub(crate) fn batch_publish_order(files: &[(PathBuf, Vec<u8>)]) -> Vec<&(PathBuf, Vec<u8>)> {
+    let (mut ordered_files, meta_files): (Vec<_>, Vec<_>) = files
+        .iter()
+        .partition(|(path, _)| path.as_path() != *META_FILEPATH);
+    ordered_files.extend(meta_files);
+    ordered_files
+}
+
 fn retry_policy(is_blocking: bool) -> RetryPolicy {
     if is_blocking {
         RetryPolicy {
@@ -176,6 +188,21 @@ pub trait Directory: DirectoryClone + fmt::Debug + Send + Sync + 'static {
     /// The file may or may not previously exist.
     fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()>;
 
+    /// Atomically replace the content of several files.
+    ///
+    /// Each file gets the same guarantees as with `atomic_write`.
+    /// Files are published in the order of the batch, except for `meta.json`
+    /// which is always published last: a reader observing the new `meta.json`
+    /// is guaranteed to observe the rest of the batch as well.
+    ///
+    /// The default implementation simply calls `atomic_write` for each file.
+    fn atomic_batch_write(&self, files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+        for (path, data) in batch_publish_order(files) {
+            self.atomic_write(path, data)?;
+        }
+        Ok(())
+    }
+
     /// Acquire a lock in the given directory.
     ///
     /// The method is blocking or not depending on the `Lock` object.
@@ -230,3 +257,9 @@ where
         Box::new(self.clone())
     }
 }
+
+impl<T: Directory + 'static> From<T> for Box<dyn Directory> {
+    fn from(directory: T) -> Self {
+        Box::new(directory)
+    }
+}
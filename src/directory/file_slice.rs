@@ -31,6 +31,23 @@ pub trait FileHandle: 'static + Send + Sync + HasLen + Debug {
         crate::info_log("warn: unoptimized read of multiple ranges");
         ranges.iter().map(|r| self.read_bytes(r.start, r.end)).collect()
     }
+
+    /// Hints that the bytes `[from..to)` are about to be read.
+    ///
+    /// Implementations backed by a cache may use it to load the range
+    /// ahead of time, in as few reads as possible. The default
+    /// implementation does nothing.
+    fn prefetch(&self, _from: Ulen, _to: Ulen) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Re-reads the length of the underlying file, for files that may grow
+    /// after they were opened, and returns it.
+    ///
+    /// The default implementation assumes the file never changes, and returns `len()`.
+    fn refresh_len(&self) -> io::Result<Ulen> {
+        Ok(self.len())
+    }
 }
 
 impl FakeArr for FileSlice {
@@ -141,6 +158,27 @@ impl FileSlice {
         self.data.read_bytes(self.start + from, self.start + to)
     }
 
+    /// Hints that the data of the `FileSlice` is about to be read.
+    ///
+    /// See [`FileHandle::prefetch`](./trait.FileHandle.html#method.prefetch).
+    pub fn prefetch(&self) -> io::Result<()> {
+        self.data.prefetch(self.start, self.stop)
+    }
+
+    /// Returns a `FileSlice` starting where this one does and extending to
+    /// the current end of the file, so that bytes appended to the file
+    /// after it was opened can be read.
+    ///
+    /// See [`FileHandle::refresh_len`](./trait.FileHandle.html#method.refresh_len).
+    pub fn refresh(&self) -> io::Result<FileSlice> {
+        let file_len = self.data.refresh_len()?;
+        Ok(FileSlice {
+            data: self.data.clone(),
+            start: self.start,
+            stop: std::cmp::max(self.start, file_len),
+        })
+    }
+
     pub fn read_bytes_slice_multiple(&self, ranges: &[Range<Ulen>]) -> io::Result<Vec<OwnedBytes>> {
         let real_ranges: Vec<Range<Ulen>> = ranges.into_iter().map(|r| (r.start + self.start)..(r.end + self.start)).collect();
         self.data.read_bytes_multiple(&real_ranges)
@@ -189,6 +227,10 @@ impl FileHandle for FileSlice {
     fn read_bytes(&self, from: Ulen, to: Ulen) -> io::Result<OwnedBytes> {
         self.read_bytes_slice(from, to)
     }
+
+    fn prefetch(&self, from: Ulen, to: Ulen) -> io::Result<()> {
+        self.data.prefetch(self.start + from, self.start + to)
+    }
 }
 
 impl HasLen for FileSlice {
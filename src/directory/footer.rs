@@ -286,7 +286,10 @@ mod tests {
     use super::FooterProxy;
     use crate::common::{BinarySerializable, VInt};
     use crate::directory::footer::{Footer, VersionedFooter};
-    use crate::directory::TerminatingWrite;
+    use crate::directory::error::OpenReadError;
+    use crate::directory::{Directory, RAMDirectory, TerminatingWrite};
+    use crate::schema::{Schema, STORED, TEXT};
+    use crate::{Index, SegmentComponent, TantivyError};
     use byteorder::{ByteOrder, LittleEndian};
     use regex::Regex;
     use std::io;
@@ -416,4 +419,70 @@ mod tests {
             or the index was created with a different & old version of tantivy."
         );
     }
+
+    #[test]
+    fn test_segment_components_reject_unknown_footer_version() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"hello"));
+        index_writer.commit()?;
+        let segment = index.searchable_segments()?[0].clone();
+        for &component in &[
+            SegmentComponent::POSTINGS,
+            SegmentComponent::TERMS,
+            SegmentComponent::STORE,
+        ] {
+            let path = segment.relative_path(component);
+            let original = directory.atomic_read(&path)?;
+            let footer_len = LittleEndian::read_u32(&original[original.len() - 4..]) as usize;
+            // The versioned footer starts with its length (a 1 byte VInt),
+            // followed by the format version.
+            let version_offset = original.len() - 4 - footer_len + 1;
+            let mut corrupted = original.clone();
+            LittleEndian::write_u32(&mut corrupted[version_offset..version_offset + 4], 99);
+            directory.atomic_write(&path, &corrupted)?;
+            assert!(matches!(
+                index.reader(),
+                Err(TantivyError::OpenReadError(
+                    OpenReadError::IncompatibleIndex(_)
+                ))
+            ));
+            directory.atomic_write(&path, &original)?;
+            assert!(index.reader().is_ok());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_segment_component_corruption_is_detected_by_checksum() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let directory = RAMDirectory::create();
+        let index = Index::create(directory.clone(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"hello"));
+        index_writer.commit()?;
+        assert!(index.validate_checksum()?.is_empty());
+        let segment = index.searchable_segments()?[0].clone();
+        for &component in &[
+            SegmentComponent::POSTINGS,
+            SegmentComponent::TERMS,
+            SegmentComponent::STORE,
+        ] {
+            let path = segment.relative_path(component);
+            let original = directory.atomic_read(&path)?;
+            let mut corrupted = original.clone();
+            corrupted[0] ^= 1u8;
+            directory.atomic_write(&path, &corrupted)?;
+            let damaged = index.validate_checksum()?;
+            assert_eq!(damaged.len(), 1);
+            assert!(damaged.contains(&path));
+            directory.atomic_write(&path, &original)?;
+            assert!(index.validate_checksum()?.is_empty());
+        }
+        Ok(())
+    }
 }
@@ -1,33 +1,204 @@
-use std::{collections::BTreeMap, convert::TryInto, fs::File, io::{BufWriter, Read, Seek, SeekFrom, Write}, ops::DerefMut, path::{Path, PathBuf}, sync::{Arc, RwLock}};
+use std::{
+    convert::TryInto,
+    fs::{self, File},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    ops::DerefMut,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex, RwLock,
+    },
+    thread,
+    time::Duration,
+};
 
+use lru::LruCache;
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
 use tantivy_fst::Ulen;
+use uuid::Uuid;
 
 use crate::{
+    core::META_FILEPATH,
     directory::{error::OpenWriteError, FileHandle, OwnedBytes, TerminatingWrite, WatchHandle},
-    Directory, HasLen,
+    Directory, HasLen, TantivyError,
 };
 
 use super::{
+    directory::batch_publish_order,
     error::{DeleteError, OpenReadError},
-    AntiCallToken, WatchCallback, WritePtr,
+    AntiCallToken, WatchCallback, WatchCallbackList, WritePtr,
 };
 
-// for demonstration purposes only: a directory that dynamically reads from the filesystem without memory mapping with an integrated cache
-// this is *not used* in my wasm demo which uses different caching and hooks into the Web APIs.
+/// Default upper bound, in bytes, of the chunk cache of a directory.
+const DEFAULT_CACHE_SIZE: Ulen = 64 * 1024 * 1024;
+
+/// Default size, in bytes, of the chunks files are read and cached by.
+const DEFAULT_CHUNK_SIZE: Ulen = 4096;
+
+/// Delay during which the filesystem events on `meta.json` are merged,
+/// so that a single commit fires a single callback.
+const WATCH_DEBOUNCE_DELAY: Duration = Duration::from_millis(50);
 
-#[derive(Debug, Clone)]
+/// Callback notified of every read an `FsDirectory` issues to the filesystem.
+///
+/// It receives the path of the file and the byte range `[from..to)` read.
+pub type ReadObserver = Arc<dyn Fn(&Path, Ulen, Ulen) + Send + Sync>;
+
+/// Directory reading files with regular file IO instead of memory mapping.
+///
+/// Files are read by chunks, which are kept in a bounded LRU cache.
+#[derive(Clone)]
 pub struct FsDirectory {
     root: PathBuf,
+    discard_writes: bool,
+    cache_size: Ulen,
+    chunk_size: Ulen,
+    cache: Arc<ChunkCache>,
+    read_observer: Option<ReadObserver>,
+    cache_hits: Arc<AtomicU64>,
+    // Started on the first call to `watch`.
+    meta_watcher: Arc<Mutex<Option<MetaWatcher>>>,
+}
+
+impl std::fmt::Debug for FsDirectory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "FsDirectory({:?})", self.root)
+    }
 }
 
 impl FsDirectory {
     pub fn new(path: &Path) -> FsDirectory {
         FsDirectory {
             root: path.to_path_buf(),
+            discard_writes: false,
+            cache_size: DEFAULT_CACHE_SIZE,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            cache: Arc::new(ChunkCache::new(DEFAULT_CACHE_SIZE, DEFAULT_CHUNK_SIZE)),
+            read_observer: None,
+            cache_hits: Arc::new(AtomicU64::new(0)),
+            meta_watcher: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Registers a callback called on every cache miss, that is every time
+    /// a file opened by this directory is actually read from the filesystem.
+    pub fn with_read_observer(mut self, read_observer: ReadObserver) -> FsDirectory {
+        self.read_observer = Some(read_observer);
+        self
+    }
+
+    /// Returns the number of chunks served from the cache
+    /// by the files opened by this directory and its clones.
+    pub fn num_cache_hits(&self) -> u64 {
+        self.cache_hits.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of bytes cached for all of the files
+    /// opened by this directory and its clones.
+    ///
+    /// Chunks are evicted in least-recently-used order once the cache is full.
+    /// At least one chunk is always cached.
+    pub fn with_cache_size(mut self, cache_size: Ulen) -> FsDirectory {
+        self.cache_size = cache_size;
+        self.cache = Arc::new(ChunkCache::new(self.cache_size, self.chunk_size));
+        self
+    }
+
+    /// Sets the size of the chunks files are read and cached by.
+    ///
+    /// Large chunks reduce the number of reads during sequential scans,
+    /// such as merges, but waste memory on small files.
+    /// The default is 4KB.
+    pub fn with_chunk_size(mut self, chunk_size: Ulen) -> FsDirectory {
+        assert!(chunk_size > 0, "The chunk size must be positive.");
+        self.chunk_size = chunk_size;
+        self.cache = Arc::new(ChunkCache::new(self.cache_size, self.chunk_size));
+        self
+    }
+
+    /// Creates an `FsDirectory` whose `open_write` hands out writers
+    /// that silently discard everything written to them.
+    ///
+    /// This is only meant for read-only demos, where segment files are never
+    /// written but tantivy still needs to open writers.
+    pub fn new_discarding_writes(path: &Path) -> FsDirectory {
+        FsDirectory {
+            discard_writes: true,
+            ..FsDirectory::new(path)
         }
     }
 }
 
+/// Writes `data` to a temporary file sitting next to `path`, and syncs it to disk.
+///
+/// Returns the path of the temporary file, which can then be renamed over `path`.
+fn stage_file(path: &Path, data: &[u8]) -> io::Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Path {:?} does not have a file name.", path),
+        )
+    })?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        Uuid::new_v4()
+    ));
+    let write_res = File::create(&temp_path).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()
+    });
+    if let Err(io_err) = write_res {
+        let _ = fs::remove_file(&temp_path);
+        return Err(io_err);
+    }
+    Ok(temp_path)
+}
+
+/// Syncs the directory at `path`, so that the renames
+/// and file creations within it are durable.
+fn sync_directory(path: &Path) -> io::Result<()> {
+    let mut open_opts = fs::OpenOptions::new();
+
+    // Linux needs read to be set, otherwise returns EINVAL
+    // write must not be set, or it fails with EISDIR
+    open_opts.read(true);
+
+    // On Windows, opening a directory requires FILE_FLAG_BACKUP_SEMANTICS
+    // and calling sync_all() only works if write access is requested.
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::OpenOptionsExt;
+        use winapi::um::winbase;
+
+        open_opts
+            .write(true)
+            .custom_flags(winbase::FILE_FLAG_BACKUP_SEMANTICS);
+    }
+
+    open_opts.open(path)?.sync_all()
+}
+
+/// Writes to a `File`, and syncs it to disk when terminated.
+struct FsFileWriter(File);
+
+impl Write for FsFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+impl TerminatingWrite for FsFileWriter {
+    fn terminate_ref(&mut self, _: AntiCallToken) -> io::Result<()> {
+        self.0.flush()?;
+        self.0.sync_all()
+    }
+}
+
 struct Noop {}
 impl Write for Noop {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
@@ -45,112 +216,787 @@ impl TerminatingWrite for Noop {
 }
 impl Directory for FsDirectory {
     fn get_file_handle(&self, path: &Path) -> Result<Box<dyn FileHandle>, OpenReadError> {
-        Ok(Box::new(FSFile::new(&self.root.join(path))))
+        let full_path = self.root.join(path);
+        let mut file = FSFile::new(&full_path, self.cache.clone()).map_err(|io_err| {
+            if io_err.kind() == io::ErrorKind::NotFound {
+                OpenReadError::FileDoesNotExist(path.to_path_buf())
+            } else {
+                OpenReadError::wrap_io_error(io_err, path.to_path_buf())
+            }
+        })?;
+        file.read_observer = self.read_observer.clone();
+        file.cache_hits = self.cache_hits.clone();
+        Ok(Box::new(file))
     }
 
     fn delete(&self, path: &Path) -> Result<(), DeleteError> {
-        println!("delete {:?}", path);
-        Ok(())
+        fs::remove_file(self.root.join(path)).map_err(|io_error| {
+            if io_error.kind() == io::ErrorKind::NotFound {
+                DeleteError::FileDoesNotExist(path.to_path_buf())
+            } else {
+                DeleteError::IOError {
+                    io_error,
+                    filepath: path.to_path_buf(),
+                }
+            }
+        })
     }
 
     fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
-        todo!()
+        let full_path = self.root.join(path);
+        match fs::metadata(&full_path) {
+            Ok(_) => Ok(true),
+            Err(io_err) if io_err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(io_err) => Err(OpenReadError::wrap_io_error(io_err, path.to_path_buf())),
+        }
     }
 
     fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
-        Ok(BufWriter::new(Box::new(Noop {})))
+        if self.discard_writes {
+            return Ok(BufWriter::new(Box::new(Noop {})));
+        }
+        let file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(self.root.join(path))
+            .map_err(|io_err| {
+                if io_err.kind() == io::ErrorKind::AlreadyExists {
+                    OpenWriteError::FileAlreadyExists(path.to_path_buf())
+                } else {
+                    OpenWriteError::wrap_io_error(io_err, path.to_path_buf())
+                }
+            })?;
+        Ok(BufWriter::new(Box::new(FsFileWriter(file))))
     }
 
     fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
-        let path = self.root.join(path);
-        println!("atomic_read {:?}", path);
-        Ok(std::fs::read(path).unwrap())
+        let full_path = self.root.join(path);
+        debug!("atomic_read {:?}", full_path);
+        fs::read(full_path).map_err(|io_err| {
+            if io_err.kind() == io::ErrorKind::NotFound {
+                OpenReadError::FileDoesNotExist(path.to_path_buf())
+            } else {
+                OpenReadError::wrap_io_error(io_err, path.to_path_buf())
+            }
+        })
     }
 
     fn atomic_write(&self, path: &Path, data: &[u8]) -> std::io::Result<()> {
-        todo!()
+        let full_path = self.root.join(path);
+        let temp_path = stage_file(&full_path, data)?;
+        fs::rename(&temp_path, &full_path).map_err(|io_err| {
+            let _ = fs::remove_file(&temp_path);
+            io_err
+        })?;
+        // The rename is only durable once the directory itself is synced.
+        sync_directory(full_path.parent().unwrap_or(&self.root))
+    }
+
+    fn atomic_batch_write(&self, files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+        // All of the files are staged before the first rename, so that a failure
+        // while writing leaves the directory untouched.
+        let mut staged_files: Vec<(PathBuf, PathBuf)> = Vec::with_capacity(files.len());
+        for (path, data) in batch_publish_order(files) {
+            let full_path = self.root.join(path);
+            match stage_file(&full_path, data) {
+                Ok(temp_path) => staged_files.push((temp_path, full_path)),
+                Err(io_err) => {
+                    for (temp_path, _) in &staged_files {
+                        let _ = fs::remove_file(temp_path);
+                    }
+                    return Err(io_err);
+                }
+            }
+        }
+        for (i, (temp_path, full_path)) in staged_files.iter().enumerate() {
+            if let Err(io_err) = fs::rename(temp_path, full_path) {
+                for (temp_path, _) in &staged_files[i..] {
+                    let _ = fs::remove_file(temp_path);
+                }
+                return Err(io_err);
+            }
+        }
+        // Each directory is synced once, after all of the renames.
+        let mut parent_dirs: Vec<&Path> = staged_files
+            .iter()
+            .map(|(_, full_path)| full_path.parent().unwrap_or(&self.root))
+            .collect();
+        parent_dirs.sort();
+        parent_dirs.dedup();
+        for parent_dir in parent_dirs {
+            sync_directory(parent_dir)?;
+        }
+        Ok(())
     }
 
+    /// Registers a callback called whenever `meta.json` is modified.
+    ///
+    /// Changes are detected with filesystem notifications, by a watcher
+    /// started on the first call and shared by the clones of this directory.
+    /// Dropping the returned `WatchHandle` unsubscribes the callback.
     fn watch(&self, watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
-        Ok(WatchHandle::empty())
+        let mut meta_watcher = self.meta_watcher.lock().unwrap();
+        if meta_watcher.is_none() {
+            *meta_watcher = Some(MetaWatcher::new(&self.root)?);
+        }
+        let callbacks = &meta_watcher.as_ref().unwrap().callbacks;
+        Ok(callbacks.subscribe(watch_callback))
+    }
+}
+
+/// Watches the `meta.json` file of a directory, and broadcasts
+/// its changes to the registered callbacks.
+///
+/// The events of a same commit are debounced, so that it fires a single callback.
+struct MetaWatcher {
+    // Dropping the watcher closes its channel, which ends the broadcasting thread.
+    _watcher: RecommendedWatcher,
+    callbacks: Arc<WatchCallbackList>,
+}
+
+impl MetaWatcher {
+    fn new(root: &Path) -> crate::Result<MetaWatcher> {
+        let watcher_error = |err: notify::Error| {
+            TantivyError::SystemError(format!("Failed to watch {:?}: {:?}", root, err))
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::watcher(tx, WATCH_DEBOUNCE_DELAY).map_err(watcher_error)?;
+        watcher
+            .watch(root, RecursiveMode::NonRecursive)
+            .map_err(watcher_error)?;
+        let callbacks: Arc<WatchCallbackList> = Default::default();
+        let thread_callbacks = callbacks.clone();
+        thread::Builder::new()
+            .name("thread-tantivy-meta-file-watcher".to_string())
+            .spawn(move || {
+                for event in rx {
+                    let changed_path = match event {
+                        DebouncedEvent::Create(path)
+                        | DebouncedEvent::Write(path)
+                        | DebouncedEvent::Rename(_, path) => path,
+                        _ => continue,
+                    };
+                    if changed_path.file_name() == META_FILEPATH.file_name() {
+                        info!("Meta file {:?} was modified", changed_path);
+                        futures::executor::block_on(thread_callbacks.broadcast());
+                    }
+                }
+            })?;
+        Ok(MetaWatcher {
+            _watcher: watcher,
+            callbacks,
+        })
+    }
+}
+
+/// Cache of the chunks read by the files of an `FsDirectory`.
+///
+/// A single cache is shared by all of the files opened by a directory
+/// and its clones, so that its size bounds the memory used by all of them.
+struct ChunkCache {
+    chunk_size: Ulen,
+    // Chunks, by file id and chunk index.
+    chunks: Mutex<LruCache<(u64, Ulen), OwnedBytes>>,
+    next_file_id: AtomicU64,
+}
+
+impl ChunkCache {
+    /// Creates a cache holding up to `cache_size` bytes of chunks of `chunk_size` bytes.
+    fn new(cache_size: Ulen, chunk_size: Ulen) -> ChunkCache {
+        let num_chunks = std::cmp::max(cache_size / chunk_size, 1);
+        ChunkCache {
+            chunk_size,
+            chunks: Mutex::new(LruCache::new(num_chunks as usize)),
+            next_file_id: AtomicU64::new(0),
+        }
     }
 }
 
-#[derive(Debug)]
 struct FSFile {
     path: PathBuf,
     file: Arc<RwLock<File>>,
-    len: Ulen,
-    cache: RwLock<BTreeMap<Ulen, Vec<u8>>>,
+    len: RwLock<Ulen>,
+    chunk_size: Ulen,
+    // Identifies the chunks of this file in the cache.
+    file_id: u64,
+    cache: Arc<ChunkCache>,
+    read_observer: Option<ReadObserver>,
+    cache_hits: Arc<AtomicU64>,
+}
+
+impl std::fmt::Debug for FSFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "FSFile({:?})", self.path)
+    }
 }
-const CS: Ulen = 4096;
 
 impl FSFile {
-    pub fn new(path: &Path) -> FSFile {
-        let mut f = File::open(path).unwrap();
-        let len = f.seek(SeekFrom::End(0)).unwrap();
-        FSFile {
+    /// Opens the file at `path`, reading it by chunks
+    /// and caching them in `cache`.
+    fn new(path: &Path, cache: Arc<ChunkCache>) -> io::Result<FSFile> {
+        let mut f = File::open(path)?;
+        let len = f.seek(SeekFrom::End(0))?;
+        Ok(FSFile {
             path: path.to_path_buf(),
             file: Arc::new(RwLock::new(f)),
-            len,
-            cache: RwLock::new(BTreeMap::new()),
-        }
+            len: RwLock::new(len),
+            chunk_size: cache.chunk_size,
+            file_id: cache.next_file_id.fetch_add(1, Ordering::Relaxed),
+            cache,
+            read_observer: None,
+            cache_hits: Arc::new(AtomicU64::new(0)),
+        })
     }
-    fn read_bytes_real(&self, from: Ulen, to: Ulen) -> Vec<u8> {
-        let len = to - from;
 
-        eprintln!("READ {} chunk {}", self.path.to_string_lossy(), from / CS);
-        if len == 51616 {
-            println!("{:?}", backtrace::Backtrace::new());
+    /// Sets the length of the file, when it is known to have changed.
+    ///
+    /// The cached chunk holding the previous end of the file may be incomplete,
+    /// so it is dropped. If the file shrank, all of its cached chunks are dropped.
+    fn set_len(&self, new_len: Ulen) {
+        let mut len = self.len.write().unwrap();
+        if new_len == *len {
+            return;
         }
-        if len > 1_000_000 {
-            println!("{:?}", backtrace::Backtrace::new());
+        let mut chunks = self.cache.chunks.lock().unwrap();
+        if new_len < *len {
+            let file_chunks: Vec<(u64, Ulen)> = chunks
+                .iter()
+                .map(|(key, _)| *key)
+                .filter(|(file_id, _)| *file_id == self.file_id)
+                .collect();
+            for key in file_chunks {
+                chunks.pop(&key);
+            }
+        } else {
+            chunks.pop(&(self.file_id, *len / self.chunk_size));
         }
-        if len > 2_000_000 {
-            panic!("tried to read too much");
+        *len = new_len;
+    }
+
+    fn read_bytes_real(&self, from: Ulen, to: Ulen) -> io::Result<Vec<u8>> {
+        trace!(
+            "Read {:?} chunk {} ({} bytes)",
+            self.path,
+            from / self.chunk_size,
+            to - from
+        );
+        if let Some(read_observer) = self.read_observer.as_ref() {
+            read_observer(&self.path, from, to);
         }
         let mut f = self.file.write().unwrap();
-        f.seek(SeekFrom::Start(from as u64)).unwrap();
-        let mut buf = Vec::with_capacity(len.try_into().unwrap());
-        let flonk = f.deref_mut();
-        (flonk).take(len as u64).read_to_end(&mut buf).unwrap();
-        return buf;
+        f.seek(SeekFrom::Start(from))?;
+        let mut buf = Vec::with_capacity((to - from) as usize);
+        f.deref_mut().take(to - from).read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn is_cached(&self, chunk_id: Ulen) -> bool {
+        self.cache
+            .chunks
+            .lock()
+            .unwrap()
+            .contains(&(self.file_id, chunk_id))
+    }
+
+    /// Returns the chunk `chunk_id`, reading it into the cache if needed.
+    ///
+    /// The cache is not locked while reading from the file.
+    fn cached_chunk(&self, chunk_id: Ulen, file_len: Ulen) -> io::Result<OwnedBytes> {
+        let key = (self.file_id, chunk_id);
+        if let Some(chunk) = self.cache.chunks.lock().unwrap().get(&key) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(chunk.clone());
+        }
+        let chunk_end = std::cmp::min((chunk_id + 1) * self.chunk_size, file_len);
+        let chunk = OwnedBytes::new(self.read_bytes_real(chunk_id * self.chunk_size, chunk_end)?);
+        self.cache.chunks.lock().unwrap().put(key, chunk.clone());
+        Ok(chunk)
     }
 }
 impl FileHandle for FSFile {
     fn read_bytes(&self, from: Ulen, to: Ulen) -> std::io::Result<OwnedBytes> {
         let len: usize = (to - from).try_into().unwrap();
-        /*eprintln!(
-            "GET {} @ {}, len {}",
-            self.path.to_string_lossy(),
-            from,
-            len
-        );*/
-        let starti = from / CS;
-        let endi = to / CS;
-        let startofs = (from % CS) as usize;
-        let endofs = (to % CS) as usize;
-        let mut out_buf = vec![0u8; len];
-        //let toget = vec![];
-        let mut cache = self.cache.write().unwrap();
-        let mut written = 0;
+        if len == 0 {
+            return Ok(OwnedBytes::empty());
+        }
+        let chunk_size = self.chunk_size;
+        let starti = from / chunk_size;
+        // Chunk holding the last byte of the range.
+        let endi = (to - 1) / chunk_size;
+        let startofs = (from % chunk_size) as usize;
+        let file_len = self.len();
+        if endi == starti {
+            // The range is within a single chunk: share it instead of copying.
+            let chunk = self.cached_chunk(starti, file_len)?;
+            return Ok(chunk.slice(startofs, startofs + len));
+        }
+        let mut out_buf = Vec::with_capacity(len);
         for i in starti..=endi {
             let startofs = if i == starti { startofs } else { 0 };
-            let endofs = if i == endi { endofs } else { CS as usize };
-            let chunk = cache.entry(i).or_insert_with(|| {
-                self.read_bytes_real(i * CS, std::cmp::min((i + 1) * CS, self.len()))
-            });
-            let chunk = &chunk[startofs..endofs];
-            let write_len = std::cmp::min(chunk.len(), len as usize);
-            out_buf[written..written + write_len].copy_from_slice(&chunk);
-            written += write_len;
+            let endofs = if i == endi {
+                (to - endi * chunk_size) as usize
+            } else {
+                chunk_size as usize
+            };
+            let chunk = self.cached_chunk(i, file_len)?;
+            out_buf.extend_from_slice(&chunk.as_slice()[startofs..endofs]);
         }
-
         Ok(OwnedBytes::new(out_buf))
     }
+
+    /// Loads the chunks of `[from..to)` missing from the cache,
+    /// with one read per run of contiguous missing chunks.
+    ///
+    /// At most as many chunks as the cache holds are loaded.
+    fn prefetch(&self, from: Ulen, to: Ulen) -> io::Result<()> {
+        let chunk_size = self.chunk_size;
+        let to = std::cmp::min(to, self.len());
+        if from >= to {
+            return Ok(());
+        }
+        let cache_capacity = self.cache.chunks.lock().unwrap().cap() as Ulen;
+        let start_chunk = from / chunk_size;
+        let end_chunk = std::cmp::min(
+            (to + chunk_size - 1) / chunk_size,
+            start_chunk + cache_capacity,
+        );
+        let mut chunk = start_chunk;
+        while chunk < end_chunk {
+            if self.is_cached(chunk) {
+                chunk += 1;
+                continue;
+            }
+            let run_start = chunk;
+            while chunk < end_chunk && !self.is_cached(chunk) {
+                chunk += 1;
+            }
+            let run_end = std::cmp::min(chunk * chunk_size, self.len());
+            let data = self.read_bytes_real(run_start * chunk_size, run_end)?;
+            let mut chunks = self.cache.chunks.lock().unwrap();
+            for (i, chunk_data) in data.chunks(chunk_size as usize).enumerate() {
+                chunks.put(
+                    (self.file_id, run_start + i as Ulen),
+                    OwnedBytes::new(chunk_data.to_vec()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Re-stats the underlying file, so that bytes appended
+    /// to it after it was opened become readable.
+    fn refresh_len(&self) -> io::Result<Ulen> {
+        let new_len = self.file.read().unwrap().metadata()?.len();
+        self.set_len(new_len);
+        Ok(new_len)
+    }
 }
 impl HasLen for FSFile {
     fn len(&self) -> Ulen {
-        self.len
+        *self.len.read().unwrap()
+    }
+}
+
+#[cfg(feature = "mmap")]
+#[cfg(test)]
+mod tests {
+    use super::{ChunkCache, FSFile, FsDirectory, DEFAULT_CHUNK_SIZE};
+    use crate::directory::error::{DeleteError, OpenReadError, OpenWriteError};
+    use crate::directory::{FileHandle, TerminatingWrite};
+    use crate::{Directory, HasLen};
+    use std::fs;
+    use std::io::{self, Write};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use tantivy_fst::Ulen;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_fs_file_refresh_len_after_growth() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let path = tempdir.path().join("growing");
+        let initial: Vec<u8> = (0..5_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &initial)?;
+        let file = FSFile::new(
+            &path,
+            Arc::new(ChunkCache::new(1 << 20, DEFAULT_CHUNK_SIZE)),
+        )?;
+        assert_eq!(file.len(), 5_000);
+        // Caches the partial tail chunk.
+        assert_eq!(file.read_bytes(4_000, 5_000)?.as_slice(), &initial[4_000..]);
+        let appended: Vec<u8> = (0..3_000u32).map(|i| (i % 13) as u8).collect();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)?
+            .write_all(&appended)?;
+        assert_eq!(file.len(), 5_000);
+        assert_eq!(file.refresh_len()?, 8_000);
+        assert_eq!(file.len(), 8_000);
+        let mut expected = initial[4_500..].to_vec();
+        expected.extend_from_slice(&appended);
+        assert_eq!(file.read_bytes(4_500, 8_000)?.as_slice(), &expected[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_read_refresh_after_growth() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        let initial: Vec<u8> = (0..5_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("growing"), &initial)?;
+        let file_slice = directory.open_read(Path::new("growing"))?;
+        assert_eq!(file_slice.len(), 5_000);
+        // Caches the partial tail chunk.
+        assert_eq!(
+            file_slice.read_bytes_slice(4_500, 5_000)?.as_slice(),
+            &initial[4_500..]
+        );
+        let appended: Vec<u8> = (0..3_000u32).map(|i| (i % 13) as u8).collect();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(tempdir.path().join("growing"))?
+            .write_all(&appended)?;
+        assert_eq!(file_slice.len(), 5_000);
+        let refreshed = file_slice.refresh()?;
+        assert_eq!(refreshed.len(), 8_000);
+        let mut expected = initial[4_500..].to_vec();
+        expected.extend_from_slice(&appended);
+        assert_eq!(
+            refreshed.read_bytes_slice(4_500, 8_000)?.as_slice(),
+            &expected[..]
+        );
+        let suffix = refreshed.slice_from(6_000).refresh()?;
+        assert_eq!(suffix.read_bytes()?.as_slice(), &appended[1_000..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fs_file_cache_is_bounded() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let path = tempdir.path().join("large");
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data)?;
+        let file = FSFile::new(
+            &path,
+            Arc::new(ChunkCache::new(4 * DEFAULT_CHUNK_SIZE, DEFAULT_CHUNK_SIZE)),
+        )?;
+        for start in (0..99_000).step_by(1_000) {
+            let end = start + 1_000;
+            let bytes = file.read_bytes(start as Ulen, end as Ulen)?;
+            assert_eq!(bytes.as_slice(), &data[start..end]);
+            assert!(file.cache.chunks.lock().unwrap().len() <= 4);
+        }
+        // Reads spanning more chunks than the cache holds are still complete.
+        let bytes = file.read_bytes(10, 90_000)?;
+        assert_eq!(bytes.as_slice(), &data[10..90_000]);
+        assert_eq!(file.cache.chunks.lock().unwrap().len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_is_shared_by_all_files() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("first"), &data)?;
+        fs::write(tempdir.path().join("second"), &data)?;
+        let directory = FsDirectory::new(tempdir.path())
+            .with_chunk_size(1_000)
+            .with_cache_size(4_000);
+        let first = directory.open_read(Path::new("first"))?;
+        let second = directory.clone().open_read(Path::new("second"))?;
+        for start in (0..10_000).step_by(1_000) {
+            let end = start + 1_000;
+            assert_eq!(
+                first.read_bytes_slice(start, end)?.as_slice(),
+                &data[start as usize..end as usize]
+            );
+            assert_eq!(
+                second.read_bytes_slice(start, end)?.as_slice(),
+                &data[start as usize..end as usize]
+            );
+            assert!(directory.cache.chunks.lock().unwrap().len() <= 4);
+        }
+        assert_eq!(directory.cache.chunks.lock().unwrap().len(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_ending_on_chunk_boundary() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("data"), &data)?;
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let directory = FsDirectory::new(tempdir.path())
+            .with_chunk_size(1_000)
+            .with_read_observer({
+                let bytes_read = bytes_read.clone();
+                Arc::new(move |_: &Path, from: Ulen, to: Ulen| {
+                    bytes_read.fetch_add(to - from, Ordering::SeqCst);
+                })
+            });
+        let file = directory.open_read(Path::new("data"))?;
+        assert_eq!(
+            file.read_bytes_slice(500, 2_000)?.as_slice(),
+            &data[500..2_000]
+        );
+        // The chunk starting at `2_000` is not read.
+        assert_eq!(bytes_read.load(Ordering::SeqCst), 2_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fs_file_read_within_chunk_shares_the_cached_chunk() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let path = tempdir.path().join("data");
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data)?;
+        let file = FSFile::new(&path, Arc::new(ChunkCache::new(1 << 20, 1_000)))?;
+        let first = file.read_bytes(2_100, 2_200)?;
+        let second = file.read_bytes(2_150, 3_000)?;
+        assert_eq!(first.as_slice(), &data[2_100..2_200]);
+        assert_eq!(second.as_slice(), &data[2_150..3_000]);
+        // Both reads are views over the same cached chunk.
+        assert_eq!(second.as_slice().as_ptr(), first.as_slice()[50..].as_ptr());
+        // Reads spanning several chunks are copied into a fresh buffer.
+        let spanning = file.read_bytes(2_900, 3_100)?;
+        assert_eq!(spanning.as_slice(), &data[2_900..3_100]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_with_custom_chunk_size() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("data"), &data)?;
+        let directory = FsDirectory::new(tempdir.path()).with_chunk_size(1_000);
+        let file = directory.open_read(Path::new("data"))?;
+        for &(start, end) in &[(0, 10_000), (999, 1_001), (1_000, 2_000), (2_500, 7_777)] {
+            let bytes = file.slice(start as Ulen, end as Ulen).read_bytes()?;
+            assert_eq!(bytes.as_slice(), &data[start..end]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fs_file_large_read() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let path = tempdir.path().join("large");
+        let data: Vec<u8> = (0..3_000_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data)?;
+        let file = FSFile::new(&path, Arc::new(ChunkCache::new(1 << 20, 1 << 22)))?;
+        let bytes = file.read_bytes(0, 3_000_000)?;
+        assert_eq!(bytes.as_slice(), &data[..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_read_missing_file() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        assert!(matches!(
+            directory.open_read(Path::new("missing")),
+            Err(OpenReadError::FileDoesNotExist(_))
+        ));
+        assert!(matches!(
+            directory.get_file_handle(Path::new("missing_dir/missing")),
+            Err(OpenReadError::FileDoesNotExist(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fs_file_prefetch() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let path = tempdir.path().join("prefetched");
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data)?;
+        let file = FSFile::new(&path, Arc::new(ChunkCache::new(1 << 20, 1_000)))?;
+        assert_eq!(
+            file.read_bytes(3_000, 3_500)?.as_slice(),
+            &data[3_000..3_500]
+        );
+        file.prefetch(1_500, 8_200)?;
+        assert_eq!(file.cache.chunks.lock().unwrap().len(), 8);
+        // Overwriting the file in place shows whether reads hit the cache.
+        fs::OpenOptions::new()
+            .write(true)
+            .open(&path)?
+            .write_all(&[0u8; 10_000])?;
+        assert_eq!(
+            file.read_bytes(1_000, 9_000)?.as_slice(),
+            &data[1_000..9_000]
+        );
+        assert_eq!(file.read_bytes(9_000, 9_100)?.as_slice(), &[0u8; 100][..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_observer() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("data"), &data)?;
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let directory = FsDirectory::new(tempdir.path())
+            .with_chunk_size(1_000)
+            .with_read_observer({
+                let bytes_read = bytes_read.clone();
+                Arc::new(move |path: &Path, from: Ulen, to: Ulen| {
+                    assert_eq!(path.file_name().unwrap(), "data");
+                    bytes_read.fetch_add(to - from, Ordering::SeqCst);
+                })
+            });
+        let file = directory.open_read(Path::new("data"))?;
+        file.read_bytes_slice(500, 2_500)?;
+        assert_eq!(bytes_read.load(Ordering::SeqCst), 3_000);
+        assert_eq!(directory.num_cache_hits(), 0);
+        file.read_bytes_slice(1_200, 1_300)?;
+        assert_eq!(bytes_read.load(Ordering::SeqCst), 3_000);
+        assert_eq!(directory.num_cache_hits(), 1);
+        file.read_bytes_slice(9_500, 9_999)?;
+        assert_eq!(bytes_read.load(Ordering::SeqCst), 4_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_write() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        let path = Path::new("meta.json");
+        directory.atomic_write(path, b"first version")?;
+        directory.atomic_write(path, b"second")?;
+        assert_eq!(directory.atomic_read(path).unwrap(), b"second");
+        // No temporary file should be left behind.
+        assert_eq!(fs::read_dir(tempdir.path())?.count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_exists() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        fs::write(tempdir.path().join("present"), b"data")?;
+        assert!(directory.exists(Path::new("present"))?);
+        assert!(!directory.exists(Path::new("absent"))?);
+        assert!(!directory.exists(Path::new("missing_dir/absent"))?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_write() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        let path = Path::new("segment.idx");
+        let mut writer = directory.open_write(path)?;
+        writer.write_all(b"hello ")?;
+        writer.write_all(b"world")?;
+        writer.terminate()?;
+        assert_eq!(fs::read(tempdir.path().join(path))?, b"hello world");
+        assert!(matches!(
+            directory.open_write(path),
+            Err(OpenWriteError::FileAlreadyExists(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_write_discarding_writes() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new_discarding_writes(tempdir.path());
+        let mut writer = directory.open_write(Path::new("segment.idx"))?;
+        writer.write_all(b"hello")?;
+        writer.terminate()?;
+        assert!(!tempdir.path().join("segment.idx").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        let path = Path::new("merged_away.idx");
+        directory.atomic_write(path, b"data")?;
+        assert!(directory.exists(path)?);
+        assert!(directory.delete(path).is_ok());
+        assert!(!directory.exists(path)?);
+        assert!(matches!(
+            directory.delete(path),
+            Err(DeleteError::FileDoesNotExist(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_batch_write() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        directory.atomic_batch_write(&[
+            (PathBuf::from("meta.json"), b"meta".to_vec()),
+            (PathBuf::from("a"), b"aaa".to_vec()),
+            (PathBuf::from("b"), b"bbb".to_vec()),
+        ])?;
+        assert_eq!(fs::read(tempdir.path().join("meta.json"))?, b"meta");
+        assert_eq!(fs::read(tempdir.path().join("a"))?, b"aaa");
+        assert_eq!(fs::read(tempdir.path().join("b"))?, b"bbb");
+        // No temporary file should be left behind.
+        assert_eq!(fs::read_dir(tempdir.path())?.count(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_batch_write_rename_failure_removes_temp_files() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        // A file cannot be renamed over a non-empty directory.
+        fs::create_dir(tempdir.path().join("b"))?;
+        fs::write(tempdir.path().join("b").join("c"), b"ccc")?;
+        assert!(directory
+            .atomic_batch_write(&[
+                (PathBuf::from("meta.json"), b"meta".to_vec()),
+                (PathBuf::from("a"), b"aaa".to_vec()),
+                (PathBuf::from("b"), b"bbb".to_vec()),
+            ])
+            .is_err());
+        assert_eq!(fs::read(tempdir.path().join("a"))?, b"aaa");
+        assert!(!tempdir.path().join("meta.json").exists());
+        // Only `a` and `b` are left: the temporary files were removed.
+        assert_eq!(fs::read_dir(tempdir.path())?.count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_atomic_batch_write_publishes_meta_last() -> io::Result<()> {
+        let tempdir = TempDir::new()?;
+        let directory = FsDirectory::new(tempdir.path());
+        let stop = Arc::new(AtomicBool::new(false));
+        let poller = {
+            let root = tempdir.path().to_path_buf();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    if let Ok(meta) = fs::read_to_string(root.join("meta.json")) {
+                        assert!(
+                            root.join(&meta).exists(),
+                            "meta.json points to {:?} which is not published yet",
+                            meta
+                        );
+                    }
+                }
+            })
+        };
+        for i in 0..100 {
+            let segment_file = format!("segment_{}", i);
+            directory.atomic_batch_write(&[
+                (
+                    PathBuf::from("meta.json"),
+                    segment_file.clone().into_bytes(),
+                ),
+                (PathBuf::from(segment_file), vec![i as u8; 1_000]),
+            ])?;
+        }
+        stop.store(true, Ordering::SeqCst);
+        assert!(poller.join().is_ok());
+        Ok(())
     }
 }
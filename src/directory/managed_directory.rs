@@ -56,15 +56,24 @@ fn save_managed_paths(
     directory: &dyn Directory,
     wlock: &RwLockWriteGuard<'_, MetaInformation>,
 ) -> io::Result<()> {
-    let mut w = serde_json::to_vec(&wlock.managed_paths)?;
-    writeln!(&mut w)?;
+    let w = serialize_managed_paths(wlock)?;
     directory.atomic_write(&MANAGED_FILEPATH, &w[..])?;
     Ok(())
 }
 
+/// Serializes the list of existing files that were created by tantivy.
+fn serialize_managed_paths(wlock: &RwLockWriteGuard<'_, MetaInformation>) -> io::Result<Vec<u8>> {
+    let mut w = serde_json::to_vec(&wlock.managed_paths)?;
+    writeln!(&mut w)?;
+    Ok(w)
+}
+
 impl ManagedDirectory {
     /// Wraps a directory as managed directory.
-    pub fn wrap<Dir: Directory>(directory: Dir) -> crate::Result<ManagedDirectory> {
+    ///
+    /// The directory can be given as a concrete type, or as a `Box<dyn Directory>`.
+    pub fn wrap<T: Into<Box<dyn Directory>>>(directory: T) -> crate::Result<ManagedDirectory> {
+        let directory: Box<dyn Directory> = directory.into();
         match directory.atomic_read(&MANAGED_FILEPATH) {
             Ok(data) => {
                 let managed_files_json = String::from_utf8_lossy(&data);
@@ -76,14 +85,14 @@ impl ManagedDirectory {
                         )
                     })?;
                 Ok(ManagedDirectory {
-                    directory: Box::new(directory),
+                    directory,
                     meta_informations: Arc::new(RwLock::new(MetaInformation {
                         managed_paths: managed_files,
                     })),
                 })
             }
             Err(OpenReadError::FileDoesNotExist(_)) => Ok(ManagedDirectory {
-                directory: Box::new(directory),
+                directory,
                 meta_informations: Arc::default(),
             }),
             io_err @ Err(OpenReadError::IOError { .. }) => Err(io_err.err().unwrap().into()),
@@ -304,6 +313,34 @@ impl Directory for ManagedDirectory {
         self.directory.atomic_write(path, data)
     }
 
+    /// Registers the files of the batch as managed, and publishes the
+    /// updated list of managed files as part of the same batch.
+    ///
+    /// The list of managed files comes first in the batch, so that it is
+    /// published before the files it registers.
+    fn atomic_batch_write(&self, files: &[(PathBuf, Vec<u8>)]) -> io::Result<()> {
+        let mut meta_wlock = self
+            .meta_informations
+            .write()
+            .expect("Managed file lock poisoned");
+        let mut has_changed = false;
+        for (path, _) in files {
+            if is_managed(path) {
+                has_changed |= meta_wlock.managed_paths.insert(path.to_owned());
+            }
+        }
+        if !has_changed {
+            return self.directory.atomic_batch_write(files);
+        }
+        let mut batch = Vec::with_capacity(files.len() + 1);
+        batch.push((
+            MANAGED_FILEPATH.to_path_buf(),
+            serialize_managed_paths(&meta_wlock)?,
+        ));
+        batch.extend_from_slice(files);
+        self.directory.atomic_batch_write(&batch)
+    }
+
     fn atomic_read(&self, path: &Path) -> result::Result<Vec<u8>, OpenReadError> {
         self.directory.atomic_read(path)
     }
@@ -379,6 +416,36 @@ mod tests_mmap_specific {
         }
     }
 
+    #[test]
+    fn test_managed_directory_atomic_batch_write() {
+        let tempdir = TempDir::new().unwrap();
+        let tempdir_path = PathBuf::from(tempdir.path());
+
+        let test_path1: &'static Path = Path::new("some_path_for_test");
+        let test_path2: &'static Path = Path::new("some_path_for_test_2");
+        {
+            let mmap_directory = MmapDirectory::open(&tempdir_path).unwrap();
+            let managed_directory = ManagedDirectory::wrap(mmap_directory).unwrap();
+            managed_directory
+                .atomic_batch_write(&[
+                    (test_path1.to_owned(), vec![0u8]),
+                    (test_path2.to_owned(), vec![1u8]),
+                ])
+                .unwrap();
+            assert!(managed_directory.exists(test_path1).unwrap());
+            assert!(managed_directory.exists(test_path2).unwrap());
+        }
+        {
+            // The files were registered as managed along with the batch.
+            let mmap_directory = MmapDirectory::open(&tempdir_path).unwrap();
+            let mut managed_directory = ManagedDirectory::wrap(mmap_directory).unwrap();
+            let living_files: HashSet<PathBuf> = [test_path1.to_owned()].iter().cloned().collect();
+            assert!(managed_directory.garbage_collect(|| living_files).is_ok());
+            assert!(managed_directory.exists(test_path1).unwrap());
+            assert!(!managed_directory.exists(test_path2).unwrap());
+        }
+    }
+
     #[test]
     fn test_managed_directory_gc_while_mmapped() {
         let test_path1: &'static Path = Path::new("some_path_for_test");
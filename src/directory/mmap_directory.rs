@@ -622,4 +622,28 @@ mod tests {
         }
         assert!(mmap_directory.get_cache_info().mmapped.is_empty());
     }
+
+    #[test]
+    fn test_mmap_directory_reads_like_fs_directory() -> crate::Result<()> {
+        let tempdir = TempDir::new()?;
+        let data: Vec<u8> = (0..20_000u32).map(|i| (i % 251) as u8).collect();
+        fs::write(tempdir.path().join("data"), &data)?;
+        fs::write(tempdir.path().join("empty"), b"")?;
+        let mmap_directory = MmapDirectory::open(tempdir.path())?;
+        let fs_directory = crate::directory::FsDirectory::new(tempdir.path());
+        let path = Path::new("data");
+        let mmap_file = mmap_directory.open_read(path)?;
+        let fs_file = fs_directory.open_read(path)?;
+        assert_eq!(mmap_file.len(), fs_file.len());
+        for &(from, to) in &[(0, 20_000), (0, 1), (4_095, 4_097), (12_345, 19_999)] {
+            assert_eq!(
+                mmap_file.read_bytes_slice(from, to)?.as_slice(),
+                fs_file.read_bytes_slice(from, to)?.as_slice()
+            );
+        }
+        let empty = Path::new("empty");
+        assert_eq!(mmap_directory.open_read(empty)?.len(), 0);
+        assert_eq!(fs_directory.open_read(empty)?.len(), 0);
+        Ok(())
+    }
 }
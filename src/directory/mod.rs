@@ -16,6 +16,7 @@ mod managed_directory;
 mod owned_bytes;
 mod ram_directory;
 mod fs_directory;
+mod union_directory;
 mod watch_event_router;
 
 /// Errors specific to the directory module.
@@ -28,6 +29,7 @@ pub(crate) use self::file_slice::{ArcBytes, WeakArcBytes};
 pub use self::file_slice::{FileHandle, FileSlice, FakeArr};
 pub use self::owned_bytes::OwnedBytes;
 pub use self::ram_directory::RAMDirectory;
+pub use self::union_directory::UnionDirectory;
 pub use self::watch_event_router::{WatchCallback, WatchCallbackList, WatchHandle};
 use std::io::{self, BufWriter, Write};
 use std::path::PathBuf;
@@ -48,7 +50,7 @@ pub struct GarbageCollectionResult {
 
 #[cfg(feature = "mmap")]
 pub use self::mmap_directory::MmapDirectory;
-pub use self::fs_directory::FsDirectory;
+pub use self::fs_directory::{FsDirectory, ReadObserver};
 
 pub use self::managed_directory::ManagedDirectory;
 
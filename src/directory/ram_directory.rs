@@ -237,6 +237,7 @@ impl Directory for RAMDirectory {
 #[cfg(test)]
 mod tests {
     use super::RAMDirectory;
+    use crate::directory::TerminatingWrite;
     use crate::Directory;
     use std::io::Write;
     use std::path::Path;
@@ -257,4 +258,21 @@ mod tests {
         assert_eq!(directory_copy.atomic_read(path_atomic).unwrap(), msg_atomic);
         assert_eq!(directory_copy.atomic_read(path_seq).unwrap(), msg_seq);
     }
+
+    #[test]
+    fn test_read_handle_outlives_delete() -> crate::Result<()> {
+        let path = Path::new("segment");
+        let directory = RAMDirectory::create();
+        let mut wrt = directory.open_write(path)?;
+        wrt.write_all(b"hello ")?;
+        wrt.write_all(b"world")?;
+        wrt.terminate()?;
+        assert!(directory.exists(path)?);
+        let file_slice = directory.open_read(path)?;
+        assert!(directory.delete(path).is_ok());
+        assert!(!directory.exists(path)?);
+        assert!(directory.open_read(path).is_err());
+        assert_eq!(file_slice.read_bytes()?.as_slice(), b"hello world");
+        Ok(())
+    }
 }
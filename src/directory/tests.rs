@@ -1,4 +1,5 @@
 use super::*;
+use crate::directory::error::OpenReadError;
 use futures::channel::oneshot;
 use futures::executor::block_on;
 use std::io::Write;
@@ -9,113 +10,85 @@ use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// Generates one `#[test]` per conformance test, running it against the
+/// directory handed out by the `with_directory` function in scope.
+///
+/// Without arguments, the entire suite is generated. A `Directory`
+/// implementation is expected to pass all of it.
+macro_rules! directory_conformance_tests {
+    () => {
+        directory_conformance_tests!(
+            test_simple,
+            test_write_create_the_file,
+            test_rewrite_forbidden,
+            test_directory_delete,
+            test_atomic_write,
+            test_atomic_read_missing_file,
+            test_atomic_batch_write,
+            test_lock_non_blocking,
+            test_lock_blocking,
+            test_watch,
+        );
+    };
+    ($($test_name:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $test_name() -> crate::Result<()> {
+                with_directory(super::$test_name)
+            }
+        )*
+    };
+}
+
 #[cfg(feature = "mmap")]
 mod mmap_directory_tests {
-    use crate::directory::MmapDirectory;
-
-    type DirectoryImpl = MmapDirectory;
-
-    fn make_directory() -> DirectoryImpl {
-        MmapDirectory::create_from_tempdir().unwrap()
-    }
-
-    #[test]
-    fn test_simple() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_simple(&directory)
-    }
-
-    #[test]
-    fn test_write_create_the_file() {
-        let directory = make_directory();
-        super::test_write_create_the_file(&directory);
-    }
-
-    #[test]
-    fn test_rewrite_forbidden() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_rewrite_forbidden(&directory)?;
-        Ok(())
-    }
+    use crate::directory::{Directory, MmapDirectory};
 
-    #[test]
-    fn test_directory_delete() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_directory_delete(&directory)?;
-        Ok(())
+    fn with_directory<R>(test: impl FnOnce(&dyn Directory) -> R) -> R {
+        let directory = MmapDirectory::create_from_tempdir().unwrap();
+        test(&directory)
     }
 
-    #[test]
-    fn test_lock_non_blocking() {
-        let directory = make_directory();
-        super::test_lock_non_blocking(&directory);
-    }
-
-    #[test]
-    fn test_lock_blocking() {
-        let directory = make_directory();
-        super::test_lock_blocking(&directory);
-    }
-
-    #[test]
-    fn test_watch() {
-        let directory = make_directory();
-        super::test_watch(&directory);
-    }
+    directory_conformance_tests!();
 }
 
 mod ram_directory_tests {
-    use crate::directory::RAMDirectory;
+    use crate::directory::{Directory, RAMDirectory};
 
-    type DirectoryImpl = RAMDirectory;
-
-    fn make_directory() -> DirectoryImpl {
-        RAMDirectory::default()
-    }
-
-    #[test]
-    fn test_simple() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_simple(&directory)
+    fn with_directory<R>(test: impl FnOnce(&dyn Directory) -> R) -> R {
+        let directory = RAMDirectory::default();
+        test(&directory)
     }
 
-    #[test]
-    fn test_write_create_the_file() {
-        let directory = make_directory();
-        super::test_write_create_the_file(&directory);
-    }
+    directory_conformance_tests!();
+}
 
-    #[test]
-    fn test_rewrite_forbidden() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_rewrite_forbidden(&directory)?;
-        Ok(())
-    }
+mod union_directory_tests {
+    use crate::directory::{Directory, RAMDirectory, UnionDirectory};
+    use std::path::Path;
 
-    #[test]
-    fn test_directory_delete() -> crate::Result<()> {
-        let directory = make_directory();
-        super::test_directory_delete(&directory)?;
-        Ok(())
+    fn with_directory<R>(test: impl FnOnce(&dyn Directory) -> R) -> R {
+        let base = RAMDirectory::create();
+        base.atomic_write(Path::new("base_file"), b"base").unwrap();
+        let directory = UnionDirectory::new(Box::new(RAMDirectory::create()), Box::new(base));
+        test(&directory)
     }
 
-    #[test]
-    fn test_lock_non_blocking() {
-        let directory = make_directory();
-        super::test_lock_non_blocking(&directory);
-    }
+    directory_conformance_tests!();
+}
 
-    #[test]
-    fn test_lock_blocking() {
-        let directory = make_directory();
-        super::test_lock_blocking(&directory);
+#[cfg(feature = "mmap")]
+mod fs_directory_tests {
+    use crate::directory::{Directory, FsDirectory};
+    use tempfile::TempDir;
+
+    fn with_directory<R>(test: impl FnOnce(&dyn Directory) -> R) -> R {
+        let tempdir = TempDir::new().unwrap();
+        let directory = FsDirectory::new(tempdir.path());
+        test(&directory)
     }
 
-    #[test]
-    fn test_watch() {
-        let directory = make_directory();
-        super::test_watch(&directory);
-    }
+    directory_conformance_tests!();
 }
 
 #[test]
@@ -152,15 +125,16 @@ fn test_rewrite_forbidden(directory: &dyn Directory) -> crate::Result<()> {
     Ok(())
 }
 
-fn test_write_create_the_file(directory: &dyn Directory) {
+fn test_write_create_the_file(directory: &dyn Directory) -> crate::Result<()> {
     let test_path: &'static Path = Path::new("some_path_for_test");
     {
         assert!(directory.open_read(test_path).is_err());
-        let _w = directory.open_write(test_path).unwrap();
-        assert!(directory.exists(test_path).unwrap());
+        let _w = directory.open_write(test_path)?;
+        assert!(directory.exists(test_path)?);
         assert!(directory.open_read(test_path).is_ok());
         assert!(directory.delete(test_path).is_ok());
     }
+    Ok(())
 }
 
 fn test_directory_delete(directory: &dyn Directory) -> crate::Result<()> {
@@ -189,7 +163,44 @@ fn test_directory_delete(directory: &dyn Directory) -> crate::Result<()> {
     Ok(())
 }
 
-fn test_watch(directory: &dyn Directory) {
+fn test_atomic_write(directory: &dyn Directory) -> crate::Result<()> {
+    let test_path: &'static Path = Path::new("some_atomic_file");
+    assert!(!directory.exists(test_path)?);
+    directory.atomic_write(test_path, b"first")?;
+    assert!(directory.exists(test_path)?);
+    assert_eq!(directory.atomic_read(test_path)?, b"first");
+    directory.atomic_write(test_path, b"second version")?;
+    assert_eq!(directory.atomic_read(test_path)?, b"second version");
+    let read_file = directory.open_read(test_path)?.read_bytes()?;
+    assert_eq!(read_file.as_slice(), b"second version");
+    mem::drop(read_file);
+    assert!(directory.delete(test_path).is_ok());
+    assert!(!directory.exists(test_path)?);
+    Ok(())
+}
+
+fn test_atomic_read_missing_file(directory: &dyn Directory) -> crate::Result<()> {
+    let test_path: &'static Path = Path::new("missing_atomic_file");
+    assert!(matches!(
+        directory.atomic_read(test_path),
+        Err(OpenReadError::FileDoesNotExist(path)) if path == test_path
+    ));
+    Ok(())
+}
+
+fn test_atomic_batch_write(directory: &dyn Directory) -> crate::Result<()> {
+    directory.atomic_batch_write(&[
+        (PathBuf::from("meta.json"), b"meta".to_vec()),
+        (PathBuf::from("a"), b"aaa".to_vec()),
+    ])?;
+    assert_eq!(directory.atomic_read(Path::new("meta.json"))?, b"meta");
+    assert_eq!(directory.atomic_read(Path::new("a"))?, b"aaa");
+    directory.atomic_batch_write(&[(PathBuf::from("a"), b"a2".to_vec())])?;
+    assert_eq!(directory.atomic_read(Path::new("a"))?, b"a2");
+    Ok(())
+}
+
+fn test_watch(directory: &dyn Directory) -> crate::Result<()> {
     let counter: Arc<AtomicUsize> = Default::default();
     let (tx, rx) = crossbeam::channel::unbounded();
     let timeout = Duration::from_millis(500);
@@ -198,8 +209,7 @@ fn test_watch(directory: &dyn Directory) {
         .watch(WatchCallback::new(move || {
             let val = counter.fetch_add(1, SeqCst);
             tx.send(val + 1).unwrap();
-        }))
-        .unwrap();
+        }))?;
 
     assert!(directory
         .atomic_write(Path::new("meta.json"), b"foo")
@@ -217,9 +227,10 @@ fn test_watch(directory: &dyn Directory) {
         .atomic_write(Path::new("meta.json"), b"qux")
         .is_ok());
     assert!(rx.recv_timeout(timeout).is_err());
+    Ok(())
 }
 
-fn test_lock_non_blocking(directory: &dyn Directory) {
+fn test_lock_non_blocking(directory: &dyn Directory) -> crate::Result<()> {
     {
         let lock_a_res = directory.acquire_lock(&Lock {
             filepath: PathBuf::from("a.lock"),
@@ -242,9 +253,10 @@ fn test_lock_non_blocking(directory: &dyn Directory) {
         is_blocking: false,
     });
     assert!(lock_a_res.is_ok());
+    Ok(())
 }
 
-fn test_lock_blocking(directory: &dyn Directory) {
+fn test_lock_blocking(directory: &dyn Directory) -> crate::Result<()> {
     let lock_a_res = directory.acquire_lock(&Lock {
         filepath: PathBuf::from("a.lock"),
         is_blocking: true,
@@ -283,4 +295,5 @@ fn test_lock_blocking(directory: &dyn Directory) {
     assert!(block_on(receiver2).is_ok());
     assert!(sender.send(()).is_ok());
     assert!(join_handle.join().is_ok());
+    Ok(())
 }
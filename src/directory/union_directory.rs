@@ -0,0 +1,217 @@
+use crate::directory::error::{DeleteError, LockError, OpenReadError, OpenWriteError};
+use crate::directory::{
+    Directory, DirectoryLock, FileHandle, Lock, WatchCallback, WatchHandle, WritePtr,
+};
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Directory layering a writable `overlay` over a read-only `base`.
+///
+/// Reads are resolved from the overlay first, and fall back to the base.
+/// Writes, locks and watches all go to the overlay, so that the base is
+/// never modified: a shared base index can be opened, and new segments,
+/// deletes and commits are written to a scratch overlay.
+///
+/// Deleting a file that only exists in the base hides it from this
+/// `UnionDirectory` (and its clones) without touching the base.
+/// Hidden files are not persisted, but the index meta files written
+/// to the overlay do not reference them anymore anyway.
+#[derive(Debug)]
+pub struct UnionDirectory {
+    overlay: Box<dyn Directory>,
+    base: Box<dyn Directory>,
+    hidden: Arc<RwLock<HashSet<PathBuf>>>,
+}
+
+impl Clone for UnionDirectory {
+    fn clone(&self) -> UnionDirectory {
+        UnionDirectory {
+            overlay: self.overlay.box_clone(),
+            base: self.base.box_clone(),
+            hidden: Arc::clone(&self.hidden),
+        }
+    }
+}
+
+impl UnionDirectory {
+    /// Creates a `UnionDirectory` writing to `overlay` and reading from
+    /// `overlay` then `base`.
+    pub fn new(overlay: Box<dyn Directory>, base: Box<dyn Directory>) -> UnionDirectory {
+        UnionDirectory {
+            overlay,
+            base,
+            hidden: Default::default(),
+        }
+    }
+
+    fn is_hidden(&self, path: &Path) -> bool {
+        self.hidden.read().unwrap().contains(path)
+    }
+
+    fn unhide(&self, path: &Path) {
+        self.hidden.write().unwrap().remove(path);
+    }
+
+    fn base_exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        if self.is_hidden(path) {
+            return Ok(false);
+        }
+        self.base.exists(path)
+    }
+}
+
+impl Directory for UnionDirectory {
+    fn get_file_handle(&self, path: &Path) -> Result<Box<dyn FileHandle>, OpenReadError> {
+        match self.overlay.get_file_handle(path) {
+            Err(OpenReadError::FileDoesNotExist(_)) if !self.is_hidden(path) => {
+                self.base.get_file_handle(path)
+            }
+            result => result,
+        }
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        match self.overlay.delete(path) {
+            Err(DeleteError::FileDoesNotExist(_)) => {
+                let base_exists =
+                    self.base_exists(path)
+                        .map_err(|open_read_error| DeleteError::IOError {
+                            io_error: io::Error::new(io::ErrorKind::Other, open_read_error),
+                            filepath: path.to_path_buf(),
+                        })?;
+                if !base_exists {
+                    return Err(DeleteError::FileDoesNotExist(path.to_path_buf()));
+                }
+                self.hidden.write().unwrap().insert(path.to_path_buf());
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        Ok(self.overlay.exists(path)? || self.base_exists(path)?)
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        let base_exists = self.base_exists(path).map_err(|open_read_error| {
+            let io_error = io::Error::new(io::ErrorKind::Other, open_read_error);
+            OpenWriteError::wrap_io_error(io_error, path.to_path_buf())
+        })?;
+        if base_exists {
+            return Err(OpenWriteError::FileAlreadyExists(path.to_path_buf()));
+        }
+        let write_ptr = self.overlay.open_write(path)?;
+        self.unhide(path);
+        Ok(write_ptr)
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        match self.overlay.atomic_read(path) {
+            Err(OpenReadError::FileDoesNotExist(_)) if !self.is_hidden(path) => {
+                self.base.atomic_read(path)
+            }
+            result => result,
+        }
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.overlay.atomic_write(path, data)?;
+        self.unhide(path);
+        Ok(())
+    }
+
+    fn acquire_lock(&self, lock: &Lock) -> Result<DirectoryLock, LockError> {
+        self.overlay.acquire_lock(lock)
+    }
+
+    fn watch(&self, watch_callback: WatchCallback) -> crate::Result<WatchHandle> {
+        self.overlay.watch(watch_callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionDirectory;
+    use crate::directory::{Directory, RAMDirectory, TerminatingWrite};
+    use crate::schema::{Schema, TEXT};
+    use crate::{Index, Term};
+    use std::io::Write;
+    use std::path::Path;
+
+    #[test]
+    fn test_union_directory_read_fallthrough() -> crate::Result<()> {
+        let base = RAMDirectory::create();
+        base.atomic_write(Path::new("shared"), b"base")?;
+        base.atomic_write(Path::new("base_only"), b"base only")?;
+        let overlay = RAMDirectory::create();
+        overlay.atomic_write(Path::new("shared"), b"overlay")?;
+        let directory = UnionDirectory::new(Box::new(overlay), Box::new(base));
+        assert_eq!(directory.atomic_read(Path::new("shared"))?, b"overlay");
+        assert_eq!(directory.atomic_read(Path::new("base_only"))?, b"base only");
+        let file = directory.open_read(Path::new("base_only"))?;
+        assert_eq!(file.read_bytes()?.as_slice(), b"base only");
+        assert!(directory.exists(Path::new("base_only"))?);
+        assert!(!directory.exists(Path::new("missing"))?);
+        assert!(directory.open_read(Path::new("missing")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_directory_write_isolation() -> crate::Result<()> {
+        let base = RAMDirectory::create();
+        base.atomic_write(Path::new("base_only"), b"base only")?;
+        let overlay = RAMDirectory::create();
+        let directory = UnionDirectory::new(Box::new(overlay.clone()), Box::new(base.clone()));
+        assert!(directory.open_write(Path::new("base_only")).is_err());
+        let mut wrt = directory.open_write(Path::new("new_file"))?;
+        wrt.write_all(b"new")?;
+        wrt.terminate()?;
+        assert!(overlay.exists(Path::new("new_file"))?);
+        assert!(!base.exists(Path::new("new_file"))?);
+
+        assert!(directory.delete(Path::new("base_only")).is_ok());
+        assert!(!directory.exists(Path::new("base_only"))?);
+        assert!(directory.open_read(Path::new("base_only")).is_err());
+        assert!(base.exists(Path::new("base_only"))?);
+        assert!(directory.delete(Path::new("base_only")).is_err());
+
+        directory.atomic_write(Path::new("base_only"), b"rewritten")?;
+        assert_eq!(directory.atomic_read(Path::new("base_only"))?, b"rewritten");
+        assert_eq!(base.atomic_read(Path::new("base_only"))?, b"base only");
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_directory_index_over_read_only_base() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let base = RAMDirectory::create();
+        {
+            let index = Index::create(base.clone(), schema)?;
+            let mut index_writer = index.writer_for_tests()?;
+            index_writer.add_document(doc!(text=>"a"));
+            index_writer.add_document(doc!(text=>"b"));
+            index_writer.commit()?;
+        }
+        let base_meta = base.atomic_read(Path::new("meta.json"))?;
+        let union_directory =
+            UnionDirectory::new(Box::new(RAMDirectory::create()), Box::new(base.clone()));
+        let index = Index::open(union_directory)?;
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"c"));
+        index_writer.delete_term(Term::from_field_text(text, "a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        assert_eq!(base.atomic_read(Path::new("meta.json"))?, base_meta);
+        let base_searcher = Index::open(base)?.reader()?.searcher();
+        assert_eq!(base_searcher.num_docs(), 2);
+        assert_eq!(base_searcher.segment_readers().len(), 1);
+        assert!(!base_searcher.segment_reader(0).has_deletes());
+        Ok(())
+    }
+}
@@ -92,6 +92,15 @@ pub enum TantivyError {
     /// Index incompatible with current version of tantivy
     #[error("{0:?}")]
     IncompatibleIndex(Incompatibility),
+    /// The index or one of its segments was written with a format version
+    /// that this version of tantivy cannot read.
+    #[error("Unsupported format version {found}, the latest supported version is {supported}")]
+    UnsupportedVersion {
+        /// Format version found on disk.
+        found: u32,
+        /// Latest format version supported by this version of tantivy.
+        supported: u32,
+    },
 }
 
 impl From<DataCorruption> for TantivyError {
@@ -186,12 +186,42 @@ impl FastValue for crate::DateTime {
     }
 }
 
+impl FastValue for bool {
+    fn from_u64(val: u64) -> Self {
+        val != 0
+    }
+
+    fn to_u64(&self) -> u64 {
+        *self as u64
+    }
+
+    fn fast_field_cardinality(field_type: &FieldType) -> Option<Cardinality> {
+        match *field_type {
+            FieldType::Bool(ref integer_options) => integer_options.get_fastfield_cardinality(),
+            _ => None,
+        }
+    }
+
+    fn as_u64(&self) -> u64 {
+        *self as u64
+    }
+
+    fn make_zero() -> Self {
+        false
+    }
+
+    fn to_type() -> Type {
+        Type::Bool
+    }
+}
+
 fn value_to_u64(value: &Value) -> u64 {
     match *value {
         Value::U64(ref val) => *val,
         Value::I64(ref val) => common::i64_to_u64(*val),
         Value::F64(ref val) => common::f64_to_u64(*val),
         Value::Date(ref datetime) => common::i64_to_u64(datetime.timestamp()),
+        Value::Bool(ref val) => *val as u64,
         _ => panic!("Expected a u64/i64/f64 field, got {:?} ", value),
     }
 }
@@ -483,6 +513,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_fastfield_random_access_from_segment_reader() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let field = schema_builder.add_u64_field("field", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let vals = [3u64, 1 << 40, 0, u64::MAX, 17, 1 << 20, 255, 256];
+        for &val in &vals {
+            index_writer.add_document(doc!(field=>val));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let fast_field_reader = searcher.segment_reader(0).fast_fields().u64(field)?;
+        assert_eq!(fast_field_reader.min_value(), 0);
+        assert_eq!(fast_field_reader.max_value(), u64::MAX);
+        for &doc in &[7u32, 3, 0, 5, 1, 6, 2, 4, 3] {
+            assert_eq!(fast_field_reader.get(doc), vals[doc as usize]);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_merge_missing_date_fast_field() {
         let mut schema_builder = Schema::builder();
@@ -569,6 +620,52 @@ mod tests {
             assert_eq!(dates[1].timestamp(), 6i64);
         }
     }
+
+    #[test]
+    fn test_boolfastfield() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let bool_field = schema_builder.add_bool_field("flag", FAST);
+        let multi_bool_field = schema_builder.add_bool_field(
+            "multi_flag",
+            IntOptions::default().set_fast(Cardinality::MultiValues),
+        );
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.set_merge_policy(Box::new(NoMergePolicy));
+        index_writer.add_document(doc!(
+            bool_field => true,
+            multi_bool_field => false,
+            multi_bool_field => true
+        ));
+        index_writer.add_document(doc!(bool_field => false));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(multi_bool_field => true));
+        index_writer.commit()?;
+        let segment_ids = index.searchable_segment_ids()?;
+        assert_eq!(segment_ids.len(), 2);
+        futures::executor::block_on(index_writer.merge(&segment_ids))?;
+        index_writer.wait_merging_threads()?;
+
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let fast_fields = searcher.segment_reader(0).fast_fields();
+        let bool_fast_field = fast_fields.bool(bool_field)?;
+        let bools_fast_field = fast_fields.bools(multi_bool_field)?;
+        assert!(fast_fields.u64(bool_field).is_err());
+        assert!(bool_fast_field.get(0u32));
+        assert!(!bool_fast_field.get(1u32));
+        assert!(!bool_fast_field.get(2u32));
+        let mut vals = vec![];
+        bools_fast_field.get_vals(0u32, &mut vals);
+        assert_eq!(&vals, &[false, true]);
+        bools_fast_field.get_vals(1u32, &mut vals);
+        assert!(vals.is_empty());
+        bools_fast_field.get_vals(2u32, &mut vals);
+        assert_eq!(&vals, &[true]);
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
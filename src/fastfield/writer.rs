@@ -38,7 +38,8 @@ impl FastFieldsWriter {
                 FieldType::I64(ref int_options)
                 | FieldType::U64(ref int_options)
                 | FieldType::F64(ref int_options)
-                | FieldType::Date(ref int_options) => {
+                | FieldType::Date(ref int_options)
+                | FieldType::Bool(ref int_options) => {
                     match int_options.get_fastfield_cardinality() {
                         Some(Cardinality::SingleValue) => {
                             let mut fast_field_writer = IntFastFieldWriter::new(field);
@@ -211,7 +211,8 @@ impl IndexMerger {
                 FieldType::U64(ref options)
                 | FieldType::I64(ref options)
                 | FieldType::F64(ref options)
-                | FieldType::Date(ref options) => match options.get_fastfield_cardinality() {
+                | FieldType::Date(ref options)
+                | FieldType::Bool(ref options) => match options.get_fastfield_cardinality() {
                     Some(Cardinality::SingleValue) => {
                         self.write_single_fast_field(field, fast_field_serializer)?;
                     }
@@ -1584,4 +1585,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_merge_rejects_mixed_postings_format_versions() -> crate::Result<()> {
+        use super::IndexMerger;
+        use crate::postings::POSTINGS_FORMAT_VERSION;
+        use crate::TantivyError;
+        let mut schema_builder = schema::Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+        let index = Index::create_in_ram(schema.clone());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(text=>"b"));
+        index_writer.commit()?;
+        let segment_metas = index.searchable_segment_metas()?;
+        assert_eq!(segment_metas.len(), 2);
+        // There is no migration path from version 1 of the postings format.
+        let v1_segment = index.segment(
+            segment_metas[0]
+                .clone()
+                .with_postings_format_version(POSTINGS_FORMAT_VERSION - 1),
+        );
+        let v2_segment = index.segment(segment_metas[1].clone());
+        let err = IndexMerger::open(schema, &[v1_segment, v2_segment])
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            TantivyError::UnsupportedVersion { found, supported }
+                if found == POSTINGS_FORMAT_VERSION - 1 && supported == POSTINGS_FORMAT_VERSION
+        ));
+        Ok(())
+    }
 }
@@ -19,6 +19,8 @@ use crate::indexer::{DefaultMergePolicy, MergePolicy};
 use crate::indexer::{MergeCandidate, MergeOperation};
 use crate::schema::Schema;
 use crate::Opstamp;
+use crate::INDEX_FORMAT_VERSION;
+use chrono::Utc;
 use futures::channel::oneshot;
 use futures::executor::{ThreadPool, ThreadPoolBuilder};
 use futures::future::Future;
@@ -50,6 +52,7 @@ pub fn save_new_metas(schema: Schema, directory: &dyn Directory) -> crate::Resul
             segments: Vec::new(),
             schema,
             opstamp: 0u64,
+            index_format_version: INDEX_FORMAT_VERSION,
             payload: None,
         },
         directory,
@@ -70,7 +73,7 @@ fn save_metas(metas: &IndexMeta, directory: &dyn Directory) -> crate::Result<()>
     let mut buffer = serde_json::to_vec_pretty(metas)?;
     // Just adding a new line at the end of the buffer.
     writeln!(&mut buffer)?;
-    directory.atomic_write(&META_FILEPATH, &buffer[..])?;
+    directory.atomic_batch_write(&[(META_FILEPATH.to_path_buf(), buffer)])?;
     debug!("Saved metas {:?}", serde_json::to_string_pretty(&metas));
     Ok(())
 }
@@ -137,8 +140,11 @@ fn merge(
     let num_docs = merger.write(segment_serializer)?;
 
     let merged_segment_id = merged_segment.id();
+    let merged_from: Vec<SegmentId> = segments.iter().map(Segment::id).collect();
 
-    let segment_meta = index.new_segment_meta(merged_segment_id, num_docs);
+    let segment_meta = index
+        .new_segment_meta(merged_segment_id, num_docs)
+        .with_merged_from(merged_from);
     Ok(SegmentEntry::new(segment_meta, delete_cursor, None))
 }
 
@@ -297,6 +303,7 @@ impl SegmentUpdater {
                 segments: commited_segment_metas,
                 schema: index.schema(),
                 opstamp,
+                index_format_version: INDEX_FORMAT_VERSION,
                 payload: commit_message,
             };
             // TODO add context to the error.
@@ -335,7 +342,15 @@ impl SegmentUpdater {
     ) -> impl Future<Output = crate::Result<()>> {
         let segment_updater: SegmentUpdater = self.clone();
         self.schedule_future(async move {
-            let segment_entries = segment_updater.purge_deletes(opstamp)?;
+            let mut segment_entries = segment_updater.purge_deletes(opstamp)?;
+            // Segments published by this commit are created now.
+            let created_at = Utc::now();
+            for segment_entry in &mut segment_entries {
+                if segment_entry.meta().created_at().is_none() {
+                    let segment_meta = segment_entry.meta().clone().with_created_at(created_at);
+                    segment_entry.set_meta(segment_meta);
+                }
+            }
             segment_updater.segment_manager.commit(segment_entries);
             segment_updater.save_metas(opstamp, payload)?;
             let _ = garbage_collect_files(segment_updater.clone()).await;
@@ -470,10 +485,9 @@ impl SegmentUpdater {
         mut after_merge_segment_entry: SegmentEntry,
     ) -> impl Future<Output = crate::Result<SegmentMeta>> {
         let segment_updater = self.clone();
-        let after_merge_segment_meta = after_merge_segment_entry.meta().clone();
-        let end_merge_future = self.schedule_future(async move {
+        self.schedule_future(async move {
             info!("End merge {:?}", after_merge_segment_entry.meta());
-            {
+            let after_merge_segment_meta = {
                 let mut delete_cursor = after_merge_segment_entry.delete_cursor().clone();
                 if let Some(delete_operation) = delete_cursor.get() {
                     let committed_opstamp = segment_updater.load_metas().opstamp;
@@ -500,6 +514,28 @@ impl SegmentUpdater {
                         }
                     }
                 }
+                // The merged segment is as recent as the most recent of the segments
+                // it was merged from. They are looked up now, as they may have been
+                // committed during the merge. Uncommitted segments get it on commit.
+                let created_at = segment_updater
+                    .segment_manager
+                    .segment_entries()
+                    .iter()
+                    .filter(|segment_entry| {
+                        merge_operation
+                            .segment_ids()
+                            .contains(&segment_entry.segment_id())
+                    })
+                    .filter_map(|segment_entry| segment_entry.meta().created_at())
+                    .max();
+                if let Some(created_at) = created_at {
+                    let segment_meta = after_merge_segment_entry
+                        .meta()
+                        .clone()
+                        .with_created_at(created_at);
+                    after_merge_segment_entry.set_meta(segment_meta);
+                }
+                let after_merge_segment_meta = after_merge_segment_entry.meta().clone();
                 let previous_metas = segment_updater.load_metas();
                 let segments_status = segment_updater
                     .segment_manager
@@ -511,11 +547,11 @@ impl SegmentUpdater {
                 }
 
                 segment_updater.consider_merge_options().await;
-            } // we drop all possible handle to a now useless `SegmentMeta`.
+                after_merge_segment_meta
+            }; // we drop all possible handle to a now useless `SegmentMeta`.
             let _ = garbage_collect_files(segment_updater).await;
-            Ok(())
-        });
-        end_merge_future.map_ok(|_| after_merge_segment_meta)
+            Ok(after_merge_segment_meta)
+        })
     }
 
     /// Wait for current merging threads.
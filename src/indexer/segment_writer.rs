@@ -72,16 +72,22 @@ impl SegmentWriter {
             .fields()
             .map(
                 |(_, field_entry): (Field, &FieldEntry)| match field_entry.field_type() {
-                    FieldType::Str(ref text_options) => text_options
-                        .get_indexing_options()
-                        .and_then(|text_index_option| {
-                            let tokenizer_name = &text_index_option.tokenizer();
-                            tokenizer_manager.get(tokenizer_name)
-                        }),
-                    _ => None,
+                    FieldType::Str(ref text_options) => {
+                        match text_options.get_indexing_options() {
+                            Some(text_index_option) => match text_index_option.analyzer() {
+                                // Fails if the tokenizer of the analyzer is not registered.
+                                Some(analyzer_config) => {
+                                    tokenizer_manager.build(analyzer_config).map(Some)
+                                }
+                                None => Ok(tokenizer_manager.get(text_index_option.tokenizer())),
+                            },
+                            None => Ok(None),
+                        }
+                    }
+                    _ => Ok(None),
                 },
             )
-            .collect();
+            .collect::<crate::Result<_>>()?;
         Ok(SegmentWriter {
             max_doc: 0,
             multifield_postings,
@@ -265,6 +271,19 @@ impl SegmentWriter {
                         }
                     }
                 }
+                FieldType::Bool(ref int_option) => {
+                    if int_option.is_indexed() {
+                        for field_value in field_values {
+                            term_buffer.set_field(field_value.field());
+                            let bool_val = field_value
+                                .value()
+                                .bool_value()
+                                .ok_or_else(make_schema_error)?;
+                            term_buffer.set_bool(bool_val);
+                            multifield_postings.subscribe(doc_id, &term_buffer);
+                        }
+                    }
+                }
                 FieldType::Bytes(ref option) => {
                     if option.is_indexed() {
                         for field_value in field_values {
@@ -346,6 +365,10 @@ impl SerializableSegment for SegmentWriter {
 #[cfg(test)]
 mod tests {
     use super::initial_table_size;
+    use crate::collector::Count;
+    use crate::query::TermQuery;
+    use crate::schema::{IndexRecordOption, Schema, Term, INDEXED, STORED, TEXT};
+    use crate::{DocAddress, Index, TantivyError};
 
     #[test]
     fn test_hashmap_size() {
@@ -354,4 +377,39 @@ mod tests {
         assert_eq!(initial_table_size(10_000_000).unwrap(), 17);
         assert_eq!(initial_table_size(1_000_000_000).unwrap(), 19);
     }
+
+    #[test]
+    fn test_field_options_are_respected() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let note = schema_builder.add_text_field("note", STORED);
+        let body = schema_builder.add_text_field("body", TEXT);
+        let count = schema_builder.add_u64_field("count", INDEXED | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(
+            title => "hello",
+            note => "hidden",
+            body => "world",
+            count => 3u64,
+        ));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let doc = searcher.doc(DocAddress(0, 0))?;
+        assert_eq!(doc.get_first(title).unwrap().text(), Some("hello"));
+        assert_eq!(doc.get_first(note).unwrap().text(), Some("hidden"));
+        assert!(doc.get_first(body).is_none());
+        assert_eq!(doc.get_first(count).unwrap().u64_value(), Some(3u64));
+        let num_hits = |term: Term| {
+            searcher.search(&TermQuery::new(term, IndexRecordOption::Basic), &Count)
+        };
+        assert_eq!(num_hits(Term::from_field_text(title, "hello"))?, 1);
+        assert_eq!(num_hits(Term::from_field_text(body, "world"))?, 1);
+        assert_eq!(num_hits(Term::from_field_u64(count, 3u64))?, 1);
+        assert!(matches!(
+            num_hits(Term::from_field_text(note, "hidden")),
+            Err(TantivyError::SchemaError(_))
+        ));
+        Ok(())
+    }
 }
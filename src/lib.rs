@@ -174,7 +174,11 @@ use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
 
 /// Index format version.
-const INDEX_FORMAT_VERSION: u32 = 3;
+///
+/// It is recorded in the `meta.json` file at every commit.
+/// Opening an index written with a newer format version fails with
+/// `TantivyError::UnsupportedVersion`.
+pub const INDEX_FORMAT_VERSION: u32 = 3;
 
 /// Structure version for the index.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -293,9 +297,10 @@ pub fn set_info_log_hook(f: impl Fn(&str) + Send + Sync + 'static) -> std::resul
 
 #[cfg(test)]
 mod tests {
+    use crate::collector::DocSetCollector;
     use crate::core::SegmentReader;
     use crate::docset::{DocSet, TERMINATED};
-    use crate::query::BooleanQuery;
+    use crate::query::{BooleanQuery, TermQuery};
     use crate::schema::*;
     use crate::DocAddress;
     use crate::Index;
@@ -687,6 +692,36 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_indexed_bool() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let flag_field = schema_builder.add_bool_field("flag", INDEXED | STORED);
+        let schema = schema_builder.build();
+
+        let index = Index::create_in_ram(schema);
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field => "a", flag_field => true));
+        index_writer.add_document(doc!(text_field => "b", flag_field => false));
+        index_writer.add_document(doc!(text_field => "c", flag_field => true));
+        index_writer.commit()?;
+        let reader = index.reader()?;
+        let searcher = reader.searcher();
+        assert_eq!(
+            searcher.search(&TermQuery::from_bool(flag_field, true), &DocSetCollector)?,
+            vec![DocAddress(0, 0), DocAddress(0, 2)]
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            searcher.search(&TermQuery::from_bool(flag_field, false), &DocSetCollector)?,
+            vec![DocAddress(0, 1)].into_iter().collect()
+        );
+        let doc = searcher.doc(DocAddress(0, 1))?;
+        assert_eq!(doc.get_first(flag_field), Some(&Value::Bool(false)));
+        Ok(())
+    }
+
     #[test]
     fn test_indexedfield_not_in_documents() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
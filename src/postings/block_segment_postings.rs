@@ -194,6 +194,18 @@ impl BlockSegmentPostings {
         self.load_block();
     }
 
+    /// Positions the postings back on their first block, as if they
+    /// had just been opened.
+    ///
+    /// This makes it possible to go over the same posting list twice
+    /// without reading its term info again.
+    pub fn rewind(&mut self) {
+        self.skip_reader.rewind();
+        self.block_max_score_cache = None;
+        self.loaded_offset = Ulen::MAX;
+        self.load_block();
+    }
+
     /// Returns the overall number of documents in the block postings.
     /// It does not take in account whether documents are deleted or not.
     ///
@@ -266,6 +278,15 @@ impl BlockSegmentPostings {
         self.load_block();
     }
 
+    /// Returns the number of documents in the blocks preceding the current block.
+    ///
+    /// After a `seek`, this is the ordinal, within the posting list,
+    /// of the first document of the block reached, so that
+    /// `doc_freq() - skipped_docs()` documents remain to be read.
+    pub fn skipped_docs(&self) -> u32 {
+        self.doc_freq - self.skip_reader.remaining_docs()
+    }
+
     pub(crate) fn position_offset(&self) -> u64 {
         self.skip_reader.position_offset()
     }
@@ -343,6 +364,24 @@ impl BlockSegmentPostings {
         self.load_block();
     }
 
+    /// Decodes the doc ids of the current block and of all of the
+    /// following blocks into `output`, in order.
+    ///
+    /// `output` is cleared first, but its capacity is reused.
+    /// After this call, the postings are exhausted.
+    pub fn read_all_into(&mut self, output: &mut Vec<DocId>) {
+        output.clear();
+        output.reserve(self.doc_freq as usize);
+        loop {
+            let docs = self.docs();
+            if docs.is_empty() {
+                break;
+            }
+            output.extend_from_slice(docs);
+            self.advance();
+        }
+    }
+
     /// Returns an empty segment postings object
     pub fn empty() -> BlockSegmentPostings {
         BlockSegmentPostings {
@@ -425,6 +464,76 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_read_all_into() {
+        let doc_ids: Vec<DocId> = (0..1_000u32).map(|i| i * 8 + (i % 7)).collect();
+        let mut expected = Vec::new();
+        let mut segment_postings =
+            SegmentPostings::from_block_postings(build_block_postings(&doc_ids), None);
+        while segment_postings.doc() != TERMINATED {
+            expected.push(segment_postings.doc());
+            segment_postings.advance();
+        }
+        assert_eq!(&expected[..], &doc_ids[..]);
+        let mut output = vec![42u32; 3];
+        let mut block_postings = build_block_postings(&doc_ids);
+        block_postings.read_all_into(&mut output);
+        assert_eq!(output, expected);
+        block_postings.read_all_into(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn test_skipped_docs_after_seek() {
+        let doc_ids: Vec<DocId> = (0..1_000u32).map(|i| i * 3).collect();
+        let mut block_postings = build_block_postings(&doc_ids);
+        assert_eq!(block_postings.skipped_docs(), 0);
+        for &target in &[0u32, 10, 383, 384, 1_500, 2_997] {
+            block_postings.seek(target);
+            let skipped = block_postings.skipped_docs() as usize;
+            let offset_in_block = block_postings
+                .docs()
+                .iter()
+                .position(|&doc| doc >= target)
+                .unwrap();
+            assert_eq!(skipped + offset_in_block, (target as usize + 2) / 3);
+        }
+        block_postings.seek(3_000);
+        block_postings.advance();
+        assert_eq!(block_postings.skipped_docs(), 1_000);
+    }
+
+    #[test]
+    fn test_seek_decodes_only_target_block() {
+        let doc_ids: Vec<DocId> = (0..10_000u32).collect();
+        let mut block_postings = build_block_postings(&doc_ids);
+        block_postings.seek(9_000);
+        let block_start = (9_000 / COMPRESSION_BLOCK_SIZE) * COMPRESSION_BLOCK_SIZE;
+        assert_eq!(block_postings.skipped_docs() as usize, block_start);
+        assert_eq!(block_postings.block_len(), COMPRESSION_BLOCK_SIZE);
+        assert_eq!(block_postings.docs()[0] as usize, block_start);
+        assert!(block_postings.docs().contains(&9_000));
+    }
+
+    #[test]
+    fn test_rewind() {
+        let doc_ids: Vec<DocId> = (0..1_000u32).map(|i| i * 2 + 1).collect();
+        let mut block_postings = build_block_postings(&doc_ids);
+        let mut first_pass = Vec::new();
+        block_postings.read_all_into(&mut first_pass);
+        assert_eq!(&first_pass[..], &doc_ids[..]);
+        block_postings.rewind();
+        assert_eq!(block_postings.skipped_docs(), 0);
+        assert_eq!(block_postings.docs()[0], 1);
+        let mut second_pass = Vec::new();
+        block_postings.read_all_into(&mut second_pass);
+        assert_eq!(first_pass, second_pass);
+        block_postings.seek(1_501);
+        block_postings.rewind();
+        block_postings.seek(1_501);
+        assert_eq!(block_postings.skipped_docs(), 640);
+    }
+
     #[test]
     fn test_skip_right_at_new_block() {
         let mut doc_ids = (0..128).collect::<Vec<u32>>();
@@ -210,6 +210,7 @@ pub mod tests {
 
     use super::*;
     use crate::TERMINATED;
+    use proptest::prelude::*;
 
     #[test]
     fn test_encode_sorted_block() {
@@ -305,6 +306,41 @@ pub mod tests {
             }
         }
     }
+
+    proptest! {
+        #[test]
+        fn test_sorted_block_round_trip(
+            offset in 0u32..1_000u32,
+            deltas in proptest::collection::vec(0u32..100_000u32, 1..=COMPRESSION_BLOCK_SIZE),
+        ) {
+            // Full blocks are bitpacked, while the last partial block is vint encoded.
+            let vals: Vec<u32> = deltas
+                .iter()
+                .scan(offset, |val, delta| {
+                    *val += delta;
+                    Some(*val)
+                })
+                .collect();
+            let mut encoder = BlockEncoder::new();
+            let mut decoder = BlockDecoder::default();
+            if vals.len() == COMPRESSION_BLOCK_SIZE {
+                let (num_bits, compressed_data) = encoder.compress_block_sorted(&vals, offset);
+                let consumed_num_bytes =
+                    decoder.uncompress_block_sorted(compressed_data, offset, num_bits);
+                prop_assert_eq!(consumed_num_bytes, compressed_data.len());
+            } else {
+                let compressed_data = encoder.compress_vint_sorted(&vals, offset);
+                let consumed_num_bytes = decoder.uncompress_vint_sorted(
+                    &compressed_data,
+                    offset,
+                    vals.len(),
+                    TERMINATED,
+                );
+                prop_assert_eq!(consumed_num_bytes, compressed_data.len());
+            }
+            prop_assert_eq!(decoder.output_array(), &vals[..]);
+        }
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
@@ -26,6 +26,16 @@ pub use self::term_info::TermInfo;
 
 pub(crate) type UnorderedTermId = u64;
 
+/// Version of the postings encoding written by this version of tantivy.
+///
+/// In version `2`, the skip data records the block-wand fieldnorm id and term freq
+/// of each bitpacked block.
+///
+/// Segments record the version they were written with in their `SegmentMeta`.
+/// Opening a segment written with another version fails with
+/// `TantivyError::UnsupportedVersion`.
+pub const POSTINGS_FORMAT_VERSION: u32 = 2;
+
 #[cfg_attr(feature = "cargo-clippy", allow(clippy::enum_variant_names))]
 #[derive(Debug, PartialEq, Clone, Copy, Eq)]
 pub(crate) enum FreqReadingOption {
@@ -517,6 +527,182 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_dense_postings_are_compact() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", crate::schema::STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..10_000 {
+            index_writer.add_document(doc!(text => "a"));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_info = searcher
+            .segment_reader(0)
+            .inverted_index(text)?
+            .get_term_info(&Term::from_field_text(text, "a"))?
+            .unwrap();
+        assert_eq!(term_info.doc_freq, 10_000);
+        let num_bytes = term_info.postings_stop_offset - term_info.postings_start_offset;
+        // Deltas are bitpacked, so dense doc ids take well under a byte each.
+        assert!(num_bytes < 10_000, "{} bytes", num_bytes);
+        Ok(())
+    }
+
+    #[test]
+    fn test_postings_term_freqs_round_trip() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let tag = schema_builder.add_text_field("tag", crate::schema::STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let expected: Vec<(DocId, u32)> = (0..300u32).map(|doc| (doc, doc % 5)).collect();
+        for &(_, term_freq) in &expected {
+            let body = iter::repeat("a b").take(term_freq as usize).collect::<Vec<_>>();
+            index_writer.add_document(doc!(text => body.join(" "), tag => "a"));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let mut postings = segment_reader
+            .inverted_index(text)?
+            .read_postings(&Term::from_field_text(text, "a"), IndexRecordOption::WithFreqs)?
+            .unwrap();
+        let mut doc_freqs = Vec::new();
+        while postings.doc() != TERMINATED {
+            doc_freqs.push((postings.doc(), postings.term_freq()));
+            postings.advance();
+        }
+        let expected_non_zero: Vec<(DocId, u32)> = expected
+            .into_iter()
+            .filter(|&(_, term_freq)| term_freq > 0)
+            .collect();
+        assert_eq!(doc_freqs, expected_non_zero);
+        // Fields indexed without frequencies report a term frequency of 1.
+        let mut postings = segment_reader
+            .inverted_index(tag)?
+            .read_postings(&Term::from_field_text(tag, "a"), IndexRecordOption::WithFreqs)?
+            .unwrap();
+        assert_eq!(postings.doc_freq(), 300);
+        while postings.doc() != TERMINATED {
+            assert_eq!(postings.term_freq(), 1);
+            postings.advance();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_positions_round_trip() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let tag = schema_builder.add_text_field("tag", crate::schema::STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "b c", tag => "x"));
+        index_writer.add_document(doc!(text => "a b c a d a", tag => "x"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let inverted_index = segment_reader.inverted_index(text)?;
+        let mut postings = inverted_index
+            .read_postings(
+                &Term::from_field_text(text, "a"),
+                IndexRecordOption::WithFreqsAndPositions,
+            )?
+            .unwrap();
+        assert_eq!(postings.doc(), 1);
+        let mut positions = Vec::new();
+        postings.positions(&mut positions);
+        assert_eq!(positions, vec![0, 3, 5]);
+        let mut postings = inverted_index
+            .read_postings(
+                &Term::from_field_text(text, "c"),
+                IndexRecordOption::WithFreqsAndPositions,
+            )?
+            .unwrap();
+        postings.positions(&mut positions);
+        assert_eq!(positions, vec![1]);
+        postings.advance();
+        postings.positions(&mut positions);
+        assert_eq!(positions, vec![2]);
+        // Fields indexed without positions do not take any space in the positions file.
+        let space_usage = segment_reader.space_usage()?;
+        let positions_usage = |field: Field| {
+            space_usage
+                .positions()
+                .fields()
+                .filter(|&(&usage_field, _)| usage_field == field)
+                .map(|(_, field_usage)| field_usage.total())
+                .sum::<u64>()
+        };
+        assert!(positions_usage(text) > 0);
+        assert_eq!(positions_usage(tag), 0);
+        Ok(())
+    }
+
+    #[cfg(feature = "mmap")]
+    fn postings_bytes_read(
+        index_path: &std::path::Path,
+        term: &Term,
+        visit: impl FnOnce(&mut dyn DocSet),
+    ) -> crate::Result<u64> {
+        use crate::directory::FsDirectory;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::Arc;
+        let bytes_read = Arc::new(AtomicU64::new(0));
+        let directory = FsDirectory::new(index_path)
+            .with_chunk_size(64)
+            .with_read_observer({
+                let bytes_read = bytes_read.clone();
+                Arc::new(move |path: &std::path::Path, from: u64, to: u64| {
+                    if path.extension().map(|ext| ext == "idx").unwrap_or(false) {
+                        bytes_read.fetch_add(to - from, Ordering::SeqCst);
+                    }
+                })
+            });
+        let searcher = Index::open(directory)?.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let mut postings = searcher
+            .segment_reader(0)
+            .inverted_index(term.field())?
+            .read_postings(term, IndexRecordOption::Basic)?
+            .unwrap();
+        let before = bytes_read.load(Ordering::SeqCst);
+        visit(&mut postings);
+        Ok(bytes_read.load(Ordering::SeqCst) - before)
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_seek_reads_fewer_postings_bytes_than_scan() -> crate::Result<()> {
+        let tempdir = tempfile::TempDir::new()?;
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", crate::schema::STRING);
+        let index = Index::create_in_dir(tempdir.path(), schema_builder.build())?;
+        let mut index_writer = index.writer_for_tests()?;
+        for _ in 0..20_000 {
+            index_writer.add_document(doc!(text => "a"));
+        }
+        index_writer.commit()?;
+        let term = Term::from_field_text(text, "a");
+        let seek_bytes = postings_bytes_read(tempdir.path(), &term, |postings| {
+            assert_eq!(postings.seek(19_990), 19_990);
+        })?;
+        let scan_bytes = postings_bytes_read(tempdir.path(), &term, |postings| {
+            while postings.doc() < 19_990 {
+                postings.advance();
+            }
+        })?;
+        assert!(
+            seek_bytes * 2 < scan_bytes,
+            "seek read {} bytes, scan read {} bytes",
+            seek_bytes,
+            scan_bytes
+        );
+        Ok(())
+    }
+
     pub fn test_skip_against_unoptimized<F: Fn() -> Box<dyn DocSet>>(
         postings_factory: F,
         targets: Vec<u32>,
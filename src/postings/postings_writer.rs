@@ -39,6 +39,7 @@ fn posting_from_field_entry(field_entry: &FieldEntry) -> Box<dyn PostingsWriter>
         | FieldType::F64(_)
         | FieldType::Date(_)
         | FieldType::Bytes(_)
+        | FieldType::Bool(_)
         | FieldType::HierarchicalFacet => SpecializedPostingsWriter::<NothingRecorder>::new_boxed(),
     }
 }
@@ -158,6 +159,7 @@ impl MultiFieldPostingsWriter {
                 }
                 FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) | FieldType::Date(_) => {}
                 FieldType::Bytes(_) => {}
+                FieldType::Bool(_) => {}
             }
 
             let postings_writer =
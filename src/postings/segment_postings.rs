@@ -306,4 +306,36 @@ mod tests {
         let all_deleted = DeleteBitSet::for_test(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11], 12);
         assert_eq!(docs.doc_freq_given_deletes(&all_deleted), 0);
     }
+
+    fn check_seek_contract(docs: &[u32]) {
+        let last_doc = *docs.last().unwrap();
+        for target in 0..last_doc + 2 {
+            let mut postings = SegmentPostings::create_from_docs(docs);
+            let expected = docs
+                .iter()
+                .cloned()
+                .find(|&doc| doc >= target)
+                .unwrap_or(TERMINATED);
+            assert_eq!(postings.seek(target), expected);
+            assert_eq!(postings.doc(), expected);
+        }
+    }
+
+    #[test]
+    fn test_seek_without_skip_list() {
+        // Posting lists shorter than a block are serialized without a skip list.
+        check_seek_contract(&[1, 3, 7, 20, 21, 50]);
+        let mut postings = SegmentPostings::create_from_docs(&[1, 3, 7, 20, 21, 50]);
+        assert_eq!(postings.seek(4), 7);
+        assert_eq!(postings.seek(7), 7);
+        assert_eq!(postings.seek(21), 21);
+        assert_eq!(postings.advance(), 50);
+        assert_eq!(postings.seek(51), TERMINATED);
+    }
+
+    #[test]
+    fn test_seek_with_skip_list() {
+        let docs: Vec<u32> = (0..300).map(|i| i * 3 + i % 2).collect();
+        check_seek_contract(&docs);
+    }
 }
@@ -236,6 +236,30 @@ impl<'a> FieldSerializer<'a> {
         Ok(())
     }
 
+    /// Serialize a batch of documents containing the current term.
+    ///
+    /// This is equivalent to calling `write_doc` for each document, and can
+    /// be called several times for the same term. Blocks are flushed,
+    /// and their skip list entries recorded, as they fill up,
+    /// so that a block may span several calls.
+    ///
+    /// `position_deltas` is the concatenation of the delta-encoded positions
+    /// of all of the documents of the batch.
+    pub fn write_docs(
+        &mut self,
+        doc_ids: &[DocId],
+        term_freqs: &[u32],
+        position_deltas: &[u32],
+    ) -> io::Result<()> {
+        assert_eq!(doc_ids.len(), term_freqs.len());
+        self.current_term_info.doc_freq += doc_ids.len() as u32;
+        self.postings_serializer.write_docs(doc_ids, term_freqs);
+        if let Some(ref mut positions_serializer) = self.positions_serializer_opt.as_mut() {
+            positions_serializer.write_all(position_deltas)?;
+        }
+        Ok(())
+    }
+
     /// Finish the serialization for this term postings.
     ///
     /// If the current block is incomplete, it need to be encoded
@@ -434,6 +458,12 @@ impl<W: Write> PostingsSerializer<W> {
         }
     }
 
+    pub fn write_docs(&mut self, doc_ids: &[DocId], term_freqs: &[u32]) {
+        for (&doc_id, &term_freq) in doc_ids.iter().zip(term_freqs.iter()) {
+            self.write_doc(doc_id, term_freq);
+        }
+    }
+
     fn close(mut self) -> io::Result<()> {
         self.postings_write.flush()
     }
@@ -484,3 +514,138 @@ impl<W: Write> PostingsSerializer<W> {
         self.last_doc_id_encoded = 0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{InvertedIndexSerializer, PostingsSerializer};
+    use crate::directory::{Directory, FileSlice};
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::postings::compression::COMPRESSION_BLOCK_SIZE;
+    use crate::postings::{BlockSegmentPostings, Postings, SegmentPostings};
+    use crate::schema::{IndexRecordOption, Schema, Term, TEXT};
+    use crate::{DocId, Index, SegmentComponent, SegmentReader};
+    use std::iter;
+
+    fn serialize(batches: &[&[DocId]]) -> Vec<u8> {
+        let doc_freq: usize = batches.iter().map(|batch| batch.len()).sum();
+        let mut buffer = Vec::new();
+        let mut postings_serializer =
+            PostingsSerializer::new(&mut buffer, 0.0, IndexRecordOption::WithFreqs, None);
+        postings_serializer.new_term(doc_freq as u32);
+        for batch in batches {
+            let term_freqs: Vec<u32> = batch.iter().map(|doc| doc % 5 + 1).collect();
+            postings_serializer.write_docs(batch, &term_freqs);
+        }
+        postings_serializer.close_term(doc_freq as u32).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_write_docs_builds_skip_list_across_appends() {
+        let docs: Vec<DocId> = (0..3 * COMPRESSION_BLOCK_SIZE as u32 + 10)
+            .map(|i| i * 3)
+            .collect();
+        // The batches do not align with block boundaries.
+        let batches: Vec<&[DocId]> = vec![&docs[..100], &docs[100..300], &docs[300..]];
+        let buffer = serialize(&batches);
+        assert_eq!(buffer, serialize(&[&docs[..]]));
+        let block_postings = BlockSegmentPostings::open(
+            docs.len() as u32,
+            FileSlice::from(buffer),
+            IndexRecordOption::WithFreqs,
+            IndexRecordOption::WithFreqs,
+        )
+        .unwrap();
+        let mut postings = SegmentPostings::from_block_postings(block_postings, None);
+        for block_id in 0..4 {
+            let target = docs[block_id * COMPRESSION_BLOCK_SIZE + 5];
+            assert_eq!(postings.seek(target - 1), target);
+            assert_eq!(postings.term_freq(), target % 5 + 1);
+        }
+        assert_eq!(postings.seek(docs[docs.len() - 1] + 1), TERMINATED);
+    }
+
+    #[test]
+    fn test_field_serializer_write_docs_round_trip() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let num_docs = 3 * COMPRESSION_BLOCK_SIZE + 10;
+        let term_freqs: Vec<u32> = (0..num_docs).map(|doc| doc as u32 % 3 + 1).collect();
+        {
+            let mut index_writer = index.writer_for_tests()?;
+            for &term_freq in &term_freqs {
+                index_writer.add_document(doc!(text => vec!["abc"; term_freq as usize].join(" ")));
+            }
+            index_writer.commit()?;
+            index_writer.wait_merging_threads()?;
+        }
+        // Rewrites the inverted index of the segment with `FieldSerializer::write_docs`,
+        // in batches that do not align with block boundaries.
+        let mut segment = index.searchable_segments()?.into_iter().next().unwrap();
+        for &component in &[
+            SegmentComponent::TERMS,
+            SegmentComponent::POSTINGS,
+            SegmentComponent::POSITIONS,
+            SegmentComponent::POSITIONSSKIP,
+        ] {
+            index
+                .directory()
+                .delete(&segment.relative_path(component))
+                .unwrap();
+        }
+        let doc_ids: Vec<DocId> = (0..num_docs as DocId).collect();
+        // The positions of each doc are `0..term_freq`.
+        let position_deltas: Vec<u32> = term_freqs
+            .iter()
+            .flat_map(|&term_freq| {
+                iter::once(0).chain(iter::repeat(1).take(term_freq as usize - 1))
+            })
+            .collect();
+        {
+            let mut serializer = InvertedIndexSerializer::open(&mut segment)?;
+            let total_num_tokens: u64 = term_freqs.iter().map(|&term_freq| term_freq as u64).sum();
+            let mut field_serializer = serializer.new_field(text, total_num_tokens, None)?;
+            field_serializer.new_term(b"abc", num_docs as u32)?;
+            let mut positions_start = 0;
+            for &(start, end) in &[(0, 100), (100, 300), (300, num_docs)] {
+                let positions_end =
+                    positions_start + term_freqs[start..end].iter().sum::<u32>() as usize;
+                field_serializer.write_docs(
+                    &doc_ids[start..end],
+                    &term_freqs[start..end],
+                    &position_deltas[positions_start..positions_end],
+                )?;
+                positions_start = positions_end;
+            }
+            field_serializer.close_term()?;
+            field_serializer.close()?;
+            serializer.close()?;
+        }
+        let segment_reader = SegmentReader::open(&segment)?;
+        let inverted_index = segment_reader.inverted_index(text)?;
+        let term = Term::from_field_text(text, "abc");
+        assert_eq!(inverted_index.doc_freq(&term)?, num_docs as u32);
+        let mut postings = inverted_index
+            .read_postings(&term, IndexRecordOption::WithFreqsAndPositions)?
+            .unwrap();
+        let mut positions = Vec::new();
+        for doc in 0..num_docs as DocId {
+            assert_eq!(postings.doc(), doc);
+            assert_eq!(postings.term_freq(), doc % 3 + 1);
+            postings.positions(&mut positions);
+            assert_eq!(positions, (0..doc % 3 + 1).collect::<Vec<u32>>());
+            postings.advance();
+        }
+        assert_eq!(postings.doc(), TERMINATED);
+        let mut postings = inverted_index
+            .read_postings(&term, IndexRecordOption::WithFreqs)?
+            .unwrap();
+        for block_id in 0..4 {
+            let target = (block_id * COMPRESSION_BLOCK_SIZE + 5) as DocId;
+            assert_eq!(postings.seek(target), target);
+            assert_eq!(postings.term_freq(), target % 3 + 1);
+        }
+        Ok(())
+    }
+}
@@ -74,6 +74,9 @@ pub(crate) struct SkipReader {
     last_doc_in_block: DocId,
     pub(crate) last_doc_in_previous_block: DocId,
     owned_read: FileSlice,
+    // Skip data and doc freq the reader was opened with, used to rewind.
+    skip_data: FileSlice,
+    doc_freq: u32,
     skip_info: IndexRecordOption,
     byte_offset: Ulen,
     remaining_docs: u32, // number of docs remaining, including the
@@ -112,7 +115,9 @@ impl SkipReader {
                 TERMINATED
             },
             last_doc_in_previous_block: 0u32,
-            owned_read: data,
+            owned_read: data.clone(),
+            skip_data: data,
+            doc_freq,
             skip_info,
             block_info: BlockInfo::VInt { num_docs: doc_freq },
             byte_offset: 0,
@@ -132,7 +137,9 @@ impl SkipReader {
             TERMINATED
         };
         self.last_doc_in_previous_block = 0u32;
-        self.owned_read = data;
+        self.owned_read = data.clone();
+        self.skip_data = data;
+        self.doc_freq = doc_freq;
         self.block_info = BlockInfo::VInt { num_docs: doc_freq };
         self.byte_offset = 0;
         self.remaining_docs = doc_freq;
@@ -142,11 +149,20 @@ impl SkipReader {
         }
     }
 
+    /// Positions the reader back on the first block.
+    pub fn rewind(&mut self) {
+        self.reset(self.skip_data.clone(), self.doc_freq);
+    }
+
     // Returns the block max score for this block if available.
     //
     // The block max score is available for all full bitpacked block,
-    // but no available for the last VInt encoded incomplete block.
+    // but no available for the last VInt encoded incomplete block,
+    // nor for fields indexed without term frequencies.
     pub fn block_max_score(&self, bm25_weight: &BM25Weight) -> Option<Score> {
+        if self.skip_info == IndexRecordOption::Basic {
+            return None;
+        }
         match self.block_info {
             BlockInfo::BitPacked {
                 block_wand_fieldnorm_id,
@@ -161,6 +177,12 @@ impl SkipReader {
         self.last_doc_in_block
     }
 
+    /// Returns the number of documents in the current block and
+    /// in the blocks following it.
+    pub(crate) fn remaining_docs(&self) -> u32 {
+        self.remaining_docs
+    }
+
     pub fn position_offset(&self) -> u64 {
         self.position_offset
     }
@@ -274,7 +296,10 @@ mod tests {
     use super::IndexRecordOption;
     use super::{SkipReader, SkipSerializer};
     use crate::directory::{FileSlice, OwnedBytes};
-    use crate::postings::compression::COMPRESSION_BLOCK_SIZE;
+    use crate::postings::compression::{compressed_block_size, COMPRESSION_BLOCK_SIZE};
+    use crate::{DocId, TERMINATED};
+    use proptest::prelude::*;
+    use tantivy_fst::Ulen;
 
     #[test]
     fn test_encode_block_wand_max_tf() {
@@ -406,4 +431,91 @@ mod tests {
         skip_reader.advance();
         assert_eq!(skip_reader.block_info(), BlockInfo::VInt { num_docs: 0u32 });
     }
+
+    /// Strictly increasing doc ids, with the number of bits
+    /// used to encode each of the full blocks.
+    fn docs_and_num_bits() -> impl Strategy<Value = (Vec<DocId>, Vec<u8>)> {
+        (
+            proptest::collection::vec(1u32..50, 0..1_000),
+            proptest::collection::vec(0u8..=32, 8),
+        )
+            .prop_map(|(deltas, num_bits)| {
+                let docs = deltas
+                    .into_iter()
+                    .scan(0u32, |doc, delta| {
+                        *doc += delta;
+                        Some(*doc)
+                    })
+                    .collect();
+                let num_blocks = 1_000 / COMPRESSION_BLOCK_SIZE;
+                (docs, num_bits.into_iter().cycle().take(num_blocks).collect())
+            })
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(300))]
+        #[test]
+        fn test_proptest_skip_seek_against_reference(
+            (docs, num_bits) in docs_and_num_bits(),
+            mut targets in proptest::collection::vec(0u32..60_000, 1..50)
+        ) {
+            targets.sort();
+            let last_docs: Vec<DocId> = docs
+                .chunks_exact(COMPRESSION_BLOCK_SIZE)
+                .map(|block| *block.last().unwrap())
+                .collect();
+            let mut skip_serializer = SkipSerializer::new();
+            for (&last_doc, &doc_num_bits) in last_docs.iter().zip(num_bits.iter()) {
+                skip_serializer.write_doc(last_doc, doc_num_bits);
+            }
+            let mut skip_reader = SkipReader::new(
+                OwnedBytes::new(skip_serializer.data().to_owned()).as_file_slice(),
+                docs.len() as u32,
+                IndexRecordOption::Basic,
+            );
+            for target in targets {
+                skip_reader.seek(target);
+                // Reference: the first full block whose last doc is >= target.
+                let block_ord = last_docs
+                    .iter()
+                    .position(|&last_doc| last_doc >= target)
+                    .unwrap_or(last_docs.len());
+                let expected_byte_offset: Ulen = num_bits[..block_ord]
+                    .iter()
+                    .map(|&doc_num_bits| compressed_block_size(doc_num_bits) as Ulen)
+                    .sum();
+                let expected_last_doc_in_previous_block = if block_ord == 0 {
+                    0
+                } else {
+                    last_docs[block_ord - 1]
+                };
+                prop_assert_eq!(skip_reader.byte_offset(), expected_byte_offset);
+                prop_assert_eq!(
+                    skip_reader.last_doc_in_previous_block,
+                    expected_last_doc_in_previous_block
+                );
+                if block_ord < last_docs.len() {
+                    prop_assert_eq!(skip_reader.last_doc_in_block(), last_docs[block_ord]);
+                    prop_assert_eq!(
+                        skip_reader.block_info(),
+                        BlockInfo::BitPacked {
+                            doc_num_bits: num_bits[block_ord],
+                            tf_num_bits: 0,
+                            tf_sum: 0,
+                            block_wand_fieldnorm_id: 0,
+                            block_wand_term_freq: 0,
+                        }
+                    );
+                } else {
+                    prop_assert_eq!(skip_reader.last_doc_in_block(), TERMINATED);
+                    prop_assert_eq!(
+                        skip_reader.block_info(),
+                        BlockInfo::VInt {
+                            num_docs: (docs.len() % COMPRESSION_BLOCK_SIZE) as u32
+                        }
+                    );
+                }
+            }
+        }
+    }
 }
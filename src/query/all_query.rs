@@ -3,13 +3,17 @@ use crate::core::SegmentReader;
 use crate::docset::{DocSet, TERMINATED};
 use crate::query::boost_query::BoostScorer;
 use crate::query::explanation::does_not_match;
-use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::query::{Explanation, Query, QueryCost, Scorer, Weight};
 use crate::DocId;
 use crate::Score;
 
 /// Query that matches all of the documents.
 ///
 /// All of the document get the score 1.0.
+///
+/// Deleted documents are filtered out by the collection step, as for any
+/// other query.
+#[doc(alias = "MatchAllQuery")]
 #[derive(Clone, Debug)]
 pub struct AllQuery;
 
@@ -37,6 +41,10 @@ impl Weight for AllWeight {
         }
         Ok(Explanation::new("AllQuery", 1.0))
     }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        Ok(QueryCost::new(u64::from(reader.num_docs()), 0))
+    }
 }
 
 /// Scorer associated to the `AllQuery` query.
@@ -73,10 +81,11 @@ impl Scorer for AllScorer {
 #[cfg(test)]
 mod tests {
     use super::AllQuery;
+    use crate::collector::{Count, DocSetCollector};
     use crate::docset::TERMINATED;
     use crate::query::Query;
-    use crate::schema::{Schema, TEXT};
-    use crate::Index;
+    use crate::schema::{Schema, STORED, STRING, TEXT};
+    use crate::{Index, Term};
 
     fn create_test_index() -> Index {
         let mut schema_builder = Schema::builder();
@@ -132,4 +141,35 @@ mod tests {
             assert_eq!(scorer.score(), 1.5);
         }
     }
+
+    #[test]
+    fn test_all_query_skips_deleted_docs() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id=>"a"));
+        index_writer.add_document(doc!(id=>"b"));
+        index_writer.commit()?;
+        index_writer.add_document(doc!(id=>"c"));
+        index_writer.add_document(doc!(id=>"d"));
+        index_writer.commit()?;
+        index_writer.delete_term(Term::from_field_text(id, "b"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 2);
+        assert_eq!(searcher.num_docs(), 3);
+        assert_eq!(searcher.search(&AllQuery, &Count)?, 3);
+        let mut ids: Vec<String> = searcher
+            .search(&AllQuery, &DocSetCollector)?
+            .into_iter()
+            .map(|doc_address| {
+                let doc = searcher.doc(doc_address)?;
+                Ok(doc.get_first(id).unwrap().text().unwrap().to_string())
+            })
+            .collect::<crate::Result<_>>()?;
+        ids.sort();
+        assert_eq!(ids, vec!["a", "c", "d"]);
+        Ok(())
+    }
 }
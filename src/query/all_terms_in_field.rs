@@ -0,0 +1,95 @@
+use crate::common::BitSet;
+use crate::core::SegmentReader;
+use crate::docset::DocSet;
+use crate::query::BitSetDocSet;
+use crate::schema::{Field, IndexRecordOption};
+use crate::DocId;
+
+/// `DocSet` of the documents of a segment having at least one term
+/// in a given field.
+///
+/// It is the union of the postings of all of the terms of the field,
+/// streamed from the term dictionary of the field.
+/// Deleted documents are not filtered out.
+pub struct AllTermsInFieldDocSet {
+    docset: BitSetDocSet,
+}
+
+impl AllTermsInFieldDocSet {
+    /// Creates the `DocSet` of the documents of `reader` with a term in `field`.
+    pub fn new(reader: &SegmentReader, field: Field) -> crate::Result<AllTermsInFieldDocSet> {
+        let mut doc_bitset = BitSet::with_max_value(reader.max_doc());
+        let inverted_index = reader.inverted_index(field)?;
+        let mut term_stream = inverted_index.terms().stream()?;
+        while term_stream.advance() {
+            let term_info = term_stream.value();
+            let mut block_segment_postings = inverted_index
+                .read_block_postings_from_terminfo(term_info, IndexRecordOption::Basic)?;
+            loop {
+                let docs = block_segment_postings.docs();
+                if docs.is_empty() {
+                    break;
+                }
+                for &doc in docs {
+                    doc_bitset.insert(doc);
+                }
+                block_segment_postings.advance();
+            }
+        }
+        Ok(AllTermsInFieldDocSet {
+            docset: BitSetDocSet::from(doc_bitset),
+        })
+    }
+}
+
+impl DocSet for AllTermsInFieldDocSet {
+    fn advance(&mut self) -> DocId {
+        self.docset.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.docset.seek(target)
+    }
+
+    fn doc(&self) -> DocId {
+        self.docset.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.docset.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AllTermsInFieldDocSet;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::schema::{Schema, STRING, TEXT};
+    use crate::{DocId, Index};
+
+    #[test]
+    fn test_all_terms_in_field_docset() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let color = schema_builder.add_text_field("color", STRING);
+        let title = schema_builder.add_text_field("title", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(color=>"red", title=>"a"));
+        index_writer.add_document(doc!(title=>"b"));
+        index_writer.add_document(doc!(color=>"green", color=>"red"));
+        index_writer.add_document(doc!(color=>"blue"));
+        index_writer.add_document(doc!(title=>"c"));
+        index_writer.add_document(doc!(color=>"blue", color=>"green", title=>"d"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let segment_reader = searcher.segment_reader(0);
+        let mut docset = AllTermsInFieldDocSet::new(segment_reader, color)?;
+        let mut docs: Vec<DocId> = Vec::new();
+        while docset.doc() != TERMINATED {
+            docs.push(docset.doc());
+            docset.advance();
+        }
+        assert_eq!(docs, vec![0, 2, 3, 5]);
+        Ok(())
+    }
+}
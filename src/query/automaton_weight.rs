@@ -1,7 +1,7 @@
 use crate::common::BitSet;
 use crate::core::SegmentReader;
 use crate::query::ConstScorer;
-use crate::query::{BitSetDocSet, Explanation};
+use crate::query::{BitSetDocSet, Explanation, QueryCost, MAX_ESTIMATED_TERM_EXPANSION};
 use crate::query::{Scorer, Weight};
 use crate::schema::{Field, IndexRecordOption};
 use crate::termdict::{TermDictionary, TermStreamer};
@@ -81,6 +81,23 @@ where
             ))
         }
     }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        let inverted_index = reader.inverted_index(self.field)?;
+        let term_dict = inverted_index.terms();
+        let mut term_stream = self.automaton_stream(term_dict)?;
+        let mut cost = QueryCost::default();
+        while term_stream.advance() {
+            if cost.num_terms >= MAX_ESTIMATED_TERM_EXPANSION {
+                // The expansion is too large to be enumerated:
+                // we assume the entire segment will be scanned.
+                cost.num_docs = cost.num_docs.max(u64::from(reader.max_doc()));
+                break;
+            }
+            cost += QueryCost::new(u64::from(term_stream.value().doc_freq), 1);
+        }
+        Ok(cost)
+    }
 }
 
 #[cfg(test)]
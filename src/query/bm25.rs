@@ -6,8 +6,10 @@ use crate::Term;
 use serde::Deserialize;
 use serde::Serialize;
 
-const K1: Score = 1.2;
-const B: Score = 0.75;
+/// Default value of the BM25 term frequency saturation parameter `k1`.
+pub(crate) const DEFAULT_K1: Score = 1.2;
+/// Default value of the BM25 length normalization parameter `b`.
+pub(crate) const DEFAULT_B: Score = 0.75;
 
 fn idf(doc_freq: u64, doc_count: u64) -> Score {
     assert!(doc_count >= doc_freq, "{} >= {}", doc_count, doc_freq);
@@ -15,15 +17,15 @@ fn idf(doc_freq: u64, doc_count: u64) -> Score {
     (1.0 + x).ln()
 }
 
-fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score) -> Score {
-    K1 * (1.0 - B + B * fieldnorm as Score / average_fieldnorm)
+fn cached_tf_component(fieldnorm: u32, average_fieldnorm: Score, k1: Score, b: Score) -> Score {
+    k1 * (1.0 - b + b * fieldnorm as Score / average_fieldnorm)
 }
 
-fn compute_tf_cache(average_fieldnorm: Score) -> [Score; 256] {
+fn compute_tf_cache(average_fieldnorm: Score, k1: Score, b: Score) -> [Score; 256] {
     let mut cache: [Score; 256] = [0.0; 256];
     for (fieldnorm_id, cache_mut) in cache.iter_mut().enumerate() {
         let fieldnorm = FieldNormReader::id_to_fieldnorm(fieldnorm_id as u8);
-        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm);
+        *cache_mut = cached_tf_component(fieldnorm, average_fieldnorm, k1, b);
     }
     cache
 }
@@ -40,6 +42,8 @@ pub struct BM25Weight {
     weight: Score,
     cache: [Score; 256],
     average_fieldnorm: Score,
+    k1: Score,
+    b: Score,
 }
 
 impl BM25Weight {
@@ -49,9 +53,38 @@ impl BM25Weight {
             weight: self.weight * boost,
             cache: self.cache,
             average_fieldnorm: self.average_fieldnorm,
+            k1: self.k1,
+            b: self.b,
         }
     }
 
+    /// Returns a copy of this weight using the given `k1` and `b`
+    /// parameters instead of the default `k1 = 1.2` and `b = 0.75`.
+    ///
+    /// `k1` controls how fast the term frequency saturates, and `b`
+    /// how much documents longer than average are penalized
+    /// (`b = 0` disables length normalization).
+    pub fn with_params(&self, k1: Score, b: Score) -> BM25Weight {
+        assert!(k1 >= 0.0, "k1 must be non-negative");
+        assert!((0.0..=1.0).contains(&b), "b must be within [0, 1]");
+        BM25Weight {
+            idf_explain: self.idf_explain.clone(),
+            weight: self.weight / (1.0 + self.k1) * (1.0 + k1),
+            cache: compute_tf_cache(self.average_fieldnorm, k1, b),
+            average_fieldnorm: self.average_fieldnorm,
+            k1,
+            b,
+        }
+    }
+
+    /// Returns true if this weight uses the default `k1` and `b` parameters.
+    ///
+    /// The block max information stored in the postings is computed with
+    /// the default parameters, and cannot be trusted otherwise.
+    pub(crate) fn has_default_params(&self) -> bool {
+        self.k1 == DEFAULT_K1 && self.b == DEFAULT_B
+    }
+
     pub fn for_terms(searcher: &Searcher, terms: &[Term]) -> crate::Result<BM25Weight> {
         assert!(!terms.is_empty(), "BM25 requires at least one term");
         let field = terms[0].field();
@@ -63,14 +96,12 @@ impl BM25Weight {
             );
         }
 
-        let mut total_num_tokens = 0u64;
-        let mut total_num_docs = 0u64;
-        for segment_reader in searcher.segment_readers() {
-            let inverted_index = segment_reader.inverted_index(field)?;
-            total_num_tokens += inverted_index.total_num_tokens();
-            total_num_docs += u64::from(segment_reader.max_doc());
-        }
-        let average_fieldnorm = total_num_tokens as Score / total_num_docs as Score;
+        let total_num_docs: u64 = searcher
+            .segment_readers()
+            .iter()
+            .map(|segment_reader| u64::from(segment_reader.max_doc()))
+            .sum();
+        let average_fieldnorm = searcher.average_fieldnorm(field)?;
 
         if terms.len() == 1 {
             let term_doc_freq = searcher.doc_freq(&terms[0])?;
@@ -107,12 +138,14 @@ impl BM25Weight {
     }
 
     pub(crate) fn new(idf_explain: Explanation, average_fieldnorm: Score) -> BM25Weight {
-        let weight = idf_explain.value() * (1.0 + K1);
+        let weight = idf_explain.value() * (1.0 + DEFAULT_K1);
         BM25Weight {
             idf_explain,
             weight,
-            cache: compute_tf_cache(average_fieldnorm),
+            cache: compute_tf_cache(average_fieldnorm, DEFAULT_K1, DEFAULT_B),
             average_fieldnorm,
+            k1: DEFAULT_K1,
+            b: DEFAULT_B,
         }
     }
 
@@ -147,8 +180,8 @@ impl BM25Weight {
         );
 
         tf_explanation.add_const("freq, occurrences of term within document", term_freq);
-        tf_explanation.add_const("k1, term saturation parameter", K1);
-        tf_explanation.add_const("b, length normalization parameter", B);
+        tf_explanation.add_const("k1, term saturation parameter", self.k1);
+        tf_explanation.add_const("b, length normalization parameter", self.b);
         tf_explanation.add_const(
             "dl, length of field",
             FieldNormReader::id_to_fieldnorm(fieldnorm_id) as Score,
@@ -156,7 +189,7 @@ impl BM25Weight {
         tf_explanation.add_const("avgdl, average length of field", self.average_fieldnorm);
 
         let mut explanation = Explanation::new("TermQuery, product of...", score);
-        explanation.add_detail(Explanation::new("(K1+1)", K1 + 1.0));
+        explanation.add_detail(Explanation::new("(K1+1)", self.k1 + 1.0));
         explanation.add_detail(self.idf_explain.clone());
         explanation.add_detail(tf_explanation);
         explanation
@@ -167,11 +200,37 @@ impl BM25Weight {
 mod tests {
 
     use super::idf;
-    use crate::{assert_nearly_equals, Score};
+    use crate::collector::TopDocs;
+    use crate::query::BooleanQuery;
+    use crate::schema::{Schema, TEXT};
+    use crate::{assert_nearly_equals, DocAddress, Index, Score, Term};
 
     #[test]
     fn test_idf() {
         let score: Score = 2.0;
         assert_nearly_equals!(idf(1, 2), score.ln());
     }
+
+    #[test]
+    fn test_rare_term_scores_above_common_term() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"common filler"));
+        index_writer.add_document(doc!(text=>"rare filler"));
+        for _ in 0..10 {
+            index_writer.add_document(doc!(text=>"common other"));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let query = BooleanQuery::new_multiterms_query(vec![
+            Term::from_field_text(text, "common"),
+            Term::from_field_text(text, "rare"),
+        ]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+        assert_eq!(top_docs[0].1, DocAddress(0, 1));
+        assert!(top_docs[0].0 > top_docs[1].0);
+        Ok(())
+    }
 }
@@ -186,6 +186,8 @@ impl BooleanQuery {
 
     /// Helper method to create a boolean query matching a given list of terms.
     /// The resulting query is a disjunction of the terms.
+    ///
+    /// The terms are scored using the default BM25 parameters.
     pub fn new_multiterms_query(terms: Vec<Term>) -> BooleanQuery {
         let occur_term_queries: Vec<(Occur, Box<dyn Query>)> = terms
             .into_iter()
@@ -198,6 +200,25 @@ impl BooleanQuery {
         BooleanQuery::new(occur_term_queries)
     }
 
+    /// Helper method to create a boolean query matching a given list of terms,
+    /// each with its own occurrence requirement.
+    ///
+    /// All of the terms are evaluated in a single pass: the `Must` terms are
+    /// intersected, the `MustNot` terms are excluded, and the scores of the
+    /// matching `Should` terms are added to the score of the document.
+    /// The terms are scored using the default BM25 parameters.
+    pub fn new_multiterms_query_with_occur(terms: Vec<(Occur, Term)>) -> BooleanQuery {
+        let occur_term_queries: Vec<(Occur, Box<dyn Query>)> = terms
+            .into_iter()
+            .map(|(occur, term)| {
+                let term_query: Box<dyn Query> =
+                    Box::new(TermQuery::new(term, IndexRecordOption::WithFreqs));
+                (occur, term_query)
+            })
+            .collect();
+        BooleanQuery::new(occur_term_queries)
+    }
+
     /// Deconstructed view of the clauses making up this query.
     pub fn clauses(&self) -> &[(Occur, Box<dyn Query>)] {
         &self.subqueries[..]
@@ -207,8 +228,9 @@ impl BooleanQuery {
 #[cfg(test)]
 mod tests {
     use super::BooleanQuery;
-    use crate::collector::DocSetCollector;
-    use crate::query::{QueryClone, TermQuery};
+    use crate::collector::tests::TEST_COLLECTOR_WITHOUT_SCORE;
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::query::{Occur, Query, QueryClone, TermQuery};
     use crate::schema::{IndexRecordOption, Schema, TEXT};
     use crate::{DocAddress, Index, Term};
 
@@ -248,6 +270,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_union_collects_each_doc_once_in_order() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut writer = index.writer_for_tests()?;
+        for doc_id in 0u32..1_000 {
+            let mut tokens = vec![];
+            if doc_id % 2 == 0 {
+                tokens.push("a");
+            }
+            if doc_id % 3 == 0 {
+                tokens.push("b");
+            }
+            if doc_id % 7 == 0 {
+                tokens.push("c");
+            }
+            writer.add_document(doc!(text=>tokens.join(" ")));
+        }
+        writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |text_str: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_str),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let query = BooleanQuery::union(vec![term_query("a"), term_query("b"), term_query("c")]);
+        let fruit = searcher.search(&query, &TEST_COLLECTOR_WITHOUT_SCORE)?;
+        let expected: Vec<DocAddress> = (0u32..1_000)
+            .filter(|doc_id| doc_id % 2 == 0 || doc_id % 3 == 0 || doc_id % 7 == 0)
+            .map(|doc_id| DocAddress(0u32, doc_id))
+            .collect();
+        assert_eq!(fruit.docs(), &expected[..]);
+        Ok(())
+    }
+
     #[test]
     fn test_intersection() -> crate::Result<()> {
         let index = create_test_index()?;
@@ -276,4 +335,86 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_intersection_of_multiple_terms() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut writer = index.writer_for_tests()?;
+        for doc_id in 0u32..1_000 {
+            let mut tokens = vec!["a"];
+            if doc_id % 3 == 0 {
+                tokens.push("b");
+            }
+            if doc_id % 5 == 0 {
+                tokens.push("c");
+            }
+            writer.add_document(doc!(text=>tokens.join(" ")));
+        }
+        writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = |text_str: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, text_str),
+                IndexRecordOption::Basic,
+            ))
+        };
+        let query =
+            BooleanQuery::intersection(vec![term_query("a"), term_query("b"), term_query("c")]);
+        let docs = searcher.search(&query, &DocSetCollector)?;
+        let expected = (0u32..1_000)
+            .filter(|doc_id| doc_id % 15 == 0)
+            .map(|doc_id| DocAddress(0u32, doc_id))
+            .collect();
+        assert_eq!(docs, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multiterms_query_with_occur() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let searcher = index.reader()?.searcher();
+        let text = index.schema().get_field("text").unwrap();
+        let term = |text_str: &str| Term::from_field_text(text, text_str);
+        let query = BooleanQuery::new_multiterms_query_with_occur(vec![
+            (Occur::Must, term("a")),
+            (Occur::Should, term("b")),
+            (Occur::Should, term("c")),
+            (Occur::MustNot, term("d")),
+        ]);
+        let docs = searcher.search(&query, &DocSetCollector)?;
+        assert_eq!(
+            docs,
+            vec![DocAddress(0u32, 1u32), DocAddress(0u32, 2u32)]
+                .into_iter()
+                .collect()
+        );
+        let term_query = |text_str: &str| -> Box<dyn Query> {
+            Box::new(TermQuery::new(term(text_str), IndexRecordOption::WithFreqs))
+        };
+        let nested_query = BooleanQuery::new(vec![
+            (Occur::Must, term_query("a")),
+            (
+                Occur::Should,
+                Box::new(BooleanQuery::union(vec![term_query("b")])),
+            ),
+            (
+                Occur::Should,
+                Box::new(BooleanQuery::union(vec![term_query("c")])),
+            ),
+            (Occur::MustNot, term_query("d")),
+        ]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        let nested_top_docs = searcher.search(&nested_query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 2);
+        for ((score, doc), (nested_score, nested_doc)) in top_docs.iter().zip(&nested_top_docs) {
+            assert_eq!(doc, nested_doc);
+            assert!((score - nested_score).abs() < 1e-5);
+        }
+        // The `Should` terms contribute to the score.
+        let must_only_score = searcher.search(&*term_query("a"), &TopDocs::with_limit(1))?[0].0;
+        assert!(top_docs[1].0 > must_only_score);
+        Ok(())
+    }
 }
@@ -7,6 +7,7 @@ use crate::query::weight::{for_each_pruning_scorer, for_each_scorer};
 use crate::query::EmptyScorer;
 use crate::query::Exclude;
 use crate::query::Occur;
+use crate::query::QueryCost;
 use crate::query::RequiredOptionalScorer;
 use crate::query::Scorer;
 use crate::query::Union;
@@ -188,6 +189,27 @@ impl Weight for BooleanWeight {
         Ok(explanation)
     }
 
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        let mut must_cost: Option<QueryCost> = None;
+        let mut should_cost = QueryCost::default();
+        let mut num_terms = 0u64;
+        for &(occur, ref subweight) in &self.weights {
+            let cost = subweight.estimate_cost(reader)?;
+            num_terms += cost.num_terms;
+            match occur {
+                Occur::Must => {
+                    must_cost = Some(must_cost.map_or(cost, |must| must.intersect(cost)));
+                }
+                Occur::Should => should_cost += cost,
+                Occur::MustNot => {}
+            }
+        }
+        // A conjunction is driven by its rarest clause, while a disjunction
+        // has to go through all of its clauses.
+        let num_docs = must_cost.map_or(should_cost.num_docs, |must| must.num_docs);
+        Ok(QueryCost::new(num_docs, num_terms))
+    }
+
     fn for_each(
         &self,
         reader: &SegmentReader,
@@ -17,6 +17,7 @@ mod tests {
     use crate::query::Intersection;
     use crate::query::Occur;
     use crate::query::Query;
+    use crate::query::QueryCost;
     use crate::query::QueryParser;
     use crate::query::RequiredOptionalScorer;
     use crate::query::Scorer;
@@ -313,4 +314,28 @@ mod tests {
         assert_nearly_equals!(explanation.value(), 0.6931472);
         Ok(())
     }
+
+    #[test]
+    pub fn test_boolean_estimate_cost() -> crate::Result<()> {
+        let (index, text_field) = aux_test_helper();
+        let query_parser = QueryParser::for_index(&index, vec![text_field]);
+        let searcher = index.reader()?.searcher();
+        // doc freqs: a=3, b=3, c=4, d=2
+        {
+            let query = query_parser.parse_query("+c +a +d").unwrap();
+            let cost = searcher.estimate_cost(&query)?;
+            assert_eq!(cost, QueryCost::new(2, 3));
+        }
+        {
+            let query = query_parser.parse_query("c a d").unwrap();
+            let cost = searcher.estimate_cost(&query)?;
+            assert_eq!(cost, QueryCost::new(9, 3));
+        }
+        {
+            let query = query_parser.parse_query("+c (a d)").unwrap();
+            let cost = searcher.estimate_cost(&query)?;
+            assert_eq!(cost, QueryCost::new(4, 3));
+        }
+        Ok(())
+    }
 }
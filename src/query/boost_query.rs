@@ -1,6 +1,6 @@
 use crate::fastfield::DeleteBitSet;
 use crate::query::explanation::does_not_match;
-use crate::query::{Explanation, Query, Scorer, Weight};
+use crate::query::{Explanation, Query, QueryCost, Scorer, Weight};
 use crate::{DocId, DocSet, Score, Searcher, SegmentReader, Term};
 use std::collections::BTreeSet;
 use std::fmt;
@@ -84,6 +84,10 @@ impl Weight for BoostWeight {
     fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
         self.weight.count(reader)
     }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        self.weight.estimate_cost(reader)
+    }
 }
 
 pub(crate) struct BoostScorer<S: Scorer> {
@@ -0,0 +1,235 @@
+use crate::query::explanation::does_not_match;
+use crate::query::{ConstScorer, Explanation, Query, QueryCost, Scorer, Weight};
+use crate::{DocId, DocSet, Score, Searcher, SegmentReader, Term};
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// `ConstScoreQuery` is a wrapper over a query that assigns the same
+/// score to every document it matches.
+///
+/// The document set matched by the `ConstScoreQuery` is strictly the same as
+/// the underlying query. The underlying query is always evaluated with scoring
+/// disabled, so that term frequencies and positions are never decoded.
+///
+/// This is useful for filtering, for instance as a `Must` clause
+/// of a `BooleanQuery`.
+pub struct ConstScoreQuery {
+    query: Box<dyn Query>,
+    score: Score,
+}
+
+impl ConstScoreQuery {
+    /// Builds a const score query.
+    pub fn new(query: Box<dyn Query>, score: Score) -> ConstScoreQuery {
+        ConstScoreQuery { query, score }
+    }
+}
+
+impl From<Box<dyn Query>> for ConstScoreQuery {
+    fn from(query: Box<dyn Query>) -> ConstScoreQuery {
+        ConstScoreQuery::new(query, 1.0)
+    }
+}
+
+impl Clone for ConstScoreQuery {
+    fn clone(&self) -> Self {
+        ConstScoreQuery {
+            query: self.query.box_clone(),
+            score: self.score,
+        }
+    }
+}
+
+impl fmt::Debug for ConstScoreQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Const(query={:?}, score={})", self.query, self.score)
+    }
+}
+
+impl Query for ConstScoreQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let inner_weight = self.query.weight(searcher, false)?;
+        Ok(if scoring_enabled {
+            Box::new(ConstWeight::new(inner_weight, self.score))
+        } else {
+            inner_weight
+        })
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        self.query.query_terms(term_set)
+    }
+}
+
+pub(crate) struct ConstWeight {
+    weight: Box<dyn Weight>,
+    score: Score,
+}
+
+impl ConstWeight {
+    pub fn new(weight: Box<dyn Weight>, score: Score) -> Self {
+        ConstWeight { weight, score }
+    }
+}
+
+impl Weight for ConstWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        let inner_scorer = self.weight.scorer(reader, boost)?;
+        Ok(Box::new(ConstScorer::new(inner_scorer, boost * self.score)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        Ok(Explanation::new("Const", self.score))
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.weight.count(reader)
+    }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        self.weight.estimate_cost(reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConstScoreQuery;
+    use crate::collector::{DocSetCollector, TopDocs};
+    use crate::postings::FreqReadingOption;
+    use crate::query::term_query::TermScorer;
+    use crate::query::{BooleanQuery, ConstScorer, Occur, Query, Scorer, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, TEXT};
+    use crate::{DocAddress, DocSet, Index, Term};
+
+    fn create_test_index() -> crate::Result<Index> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text=>"a b"));
+        index_writer.add_document(doc!(text=>"a a a c"));
+        index_writer.add_document(doc!(text=>"b c"));
+        index_writer.add_document(doc!(text=>"a"));
+        index_writer.commit()?;
+        Ok(index)
+    }
+
+    #[test]
+    fn test_const_score_query_same_docset() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(text, "a"),
+            IndexRecordOption::WithFreqs,
+        );
+        let const_query = ConstScoreQuery::new(Box::new(term_query.clone()), 2.5);
+        assert_eq!(
+            searcher.search(&const_query, &DocSetCollector)?,
+            searcher.search(&term_query, &DocSetCollector)?
+        );
+        let top_docs = searcher.search(&const_query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 3);
+        for (score, _) in &top_docs {
+            assert_eq!(*score, 2.5);
+        }
+        assert_eq!(const_query.count(&searcher)?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_score_query_does_not_decode_freqs() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let const_query = ConstScoreQuery::new(
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, "a"),
+                IndexRecordOption::WithFreqs,
+            )),
+            1.0,
+        );
+        let weight = const_query.weight(&searcher, false)?;
+        let scorer = weight.scorer(searcher.segment_reader(0), 1.0)?;
+        let mut term_scorer = *scorer.downcast::<TermScorer>().map_err(|_| ()).unwrap();
+        assert_eq!(term_scorer.freq_reading_option(), FreqReadingOption::NoFreq);
+        // "a" appears three times in doc 1, but its frequency is never decoded.
+        assert_eq!(term_scorer.seek(1), 1);
+        assert_eq!(term_scorer.term_freq(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_score_query_with_scoring_does_not_decode_freqs() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let const_query = ConstScoreQuery::new(
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, "a"),
+                IndexRecordOption::WithFreqs,
+            )),
+            2.0,
+        );
+        let weight = const_query.weight(&searcher, true)?;
+        let scorer = weight.scorer(searcher.segment_reader(0), 1.0)?;
+        let const_scorer = *scorer
+            .downcast::<ConstScorer<Box<dyn Scorer>>>()
+            .map_err(|_| ())
+            .unwrap();
+        let inner_scorer = const_scorer.into_inner();
+        let mut term_scorer = *inner_scorer
+            .downcast::<TermScorer>()
+            .map_err(|_| ())
+            .unwrap();
+        assert_eq!(term_scorer.freq_reading_option(), FreqReadingOption::NoFreq);
+        assert_eq!(term_scorer.seek(1), 1);
+        assert_eq!(term_scorer.term_freq(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_score_query_as_must_clause() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let scored: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_text(text, "c"),
+            IndexRecordOption::WithFreqs,
+        ));
+        let filter: Box<dyn Query> = Box::new(ConstScoreQuery::new(
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, "a"),
+                IndexRecordOption::WithFreqs,
+            )),
+            0.0,
+        ));
+        let query = BooleanQuery::from(vec![(Occur::Must, scored), (Occur::Must, filter)]);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(10))?;
+        assert_eq!(top_docs.len(), 1);
+        assert_eq!(top_docs[0].1, DocAddress(0, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_score_query_explain() -> crate::Result<()> {
+        let index = create_test_index()?;
+        let text = index.schema().get_field("text").unwrap();
+        let searcher = index.reader()?.searcher();
+        let query = ConstScoreQuery::new(
+            Box::new(TermQuery::new(
+                Term::from_field_text(text, "b"),
+                IndexRecordOption::WithFreqs,
+            )),
+            0.5,
+        );
+        let explanation = query.explain(&searcher, DocAddress(0, 2))?;
+        assert_eq!(explanation.value(), 0.5);
+        assert!(query.explain(&searcher, DocAddress(0, 1)).is_err());
+        Ok(())
+    }
+}
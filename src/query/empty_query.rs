@@ -2,7 +2,7 @@ use super::Scorer;
 use crate::docset::TERMINATED;
 use crate::query::explanation::does_not_match;
 use crate::query::Weight;
-use crate::query::{Explanation, Query};
+use crate::query::{Explanation, Query, QueryCost};
 use crate::DocId;
 use crate::DocSet;
 use crate::Score;
@@ -41,6 +41,10 @@ impl Weight for EmptyWeight {
     fn explain(&self, _reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
         Err(does_not_match(doc))
     }
+
+    fn estimate_cost(&self, _reader: &SegmentReader) -> crate::Result<QueryCost> {
+        Ok(QueryCost::default())
+    }
 }
 
 /// `EmptyScorer` is a dummy `Scorer` in which no document matches.
@@ -0,0 +1,332 @@
+use crate::fastfield::{DeleteBitSet, FastFieldReader};
+use crate::query::explanation::does_not_match;
+use crate::query::{Explanation, Query, QueryCost, Scorer, Weight};
+use crate::schema::{Field, FieldType};
+use crate::{DateTime, DocId, DocSet, Score, Searcher, SegmentReader, TantivyError, Term};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Arc;
+
+type ScoreCombiner = Arc<dyn Fn(Score, f64) -> Score + Send + Sync>;
+
+/// `FunctionScoreQuery` is a wrapper over a query that combines its score
+/// with the value of a single-valued fast field.
+///
+/// The document set matched by the `FunctionScoreQuery` is strictly the same
+/// as the underlying query. The score of each document is
+/// `combiner(underlying_score, fast_field_value)`.
+///
+/// The fast field may be a `u64`, `i64`, `f64` or date fast field.
+/// Its value is converted to a `f64` before being handed to the combiner.
+/// Dates are converted to their timestamp, in seconds.
+///
+/// ```rust
+/// use tantivy::query::{FunctionScoreQuery, Query, TermQuery};
+/// use tantivy::schema::{IndexRecordOption, Schema, FAST, TEXT};
+/// use tantivy::Term;
+///
+/// let mut schema_builder = Schema::builder();
+/// let title = schema_builder.add_text_field("title", TEXT);
+/// let popularity = schema_builder.add_u64_field("popularity", FAST);
+/// let term_query: Box<dyn Query> = Box::new(TermQuery::new(
+///     Term::from_field_text(title, "diary"),
+///     IndexRecordOption::WithFreqs,
+/// ));
+/// // score * log(1 + popularity)
+/// let query = FunctionScoreQuery::log1p_product(term_query.box_clone(), popularity);
+/// // custom combination
+/// let query = FunctionScoreQuery::new(term_query, popularity, |score, popularity| {
+///     score + 0.1 * popularity as f32
+/// });
+/// ```
+pub struct FunctionScoreQuery {
+    query: Box<dyn Query>,
+    field: Field,
+    combiner: ScoreCombiner,
+}
+
+impl FunctionScoreQuery {
+    /// Builds a function score query, combining the underlying query score
+    /// and the fast field value using `combiner`.
+    pub fn new<F>(query: Box<dyn Query>, field: Field, combiner: F) -> FunctionScoreQuery
+    where
+        F: Fn(Score, f64) -> Score + Send + Sync + 'static,
+    {
+        FunctionScoreQuery {
+            query,
+            field,
+            combiner: Arc::new(combiner),
+        }
+    }
+
+    /// Builds a function score query multiplying the underlying query score
+    /// by the fast field value.
+    pub fn product(query: Box<dyn Query>, field: Field) -> FunctionScoreQuery {
+        FunctionScoreQuery::new(query, field, |score, value| score * value as Score)
+    }
+
+    /// Builds a function score query adding the fast field value
+    /// to the underlying query score.
+    pub fn sum(query: Box<dyn Query>, field: Field) -> FunctionScoreQuery {
+        FunctionScoreQuery::new(query, field, |score, value| score + value as Score)
+    }
+
+    /// Builds a function score query multiplying the underlying query score
+    /// by `ln(1 + value)`.
+    ///
+    /// This is a common way to blend relevance with a popularity signal,
+    /// without letting very popular documents crush everything else.
+    pub fn log1p_product(query: Box<dyn Query>, field: Field) -> FunctionScoreQuery {
+        FunctionScoreQuery::new(query, field, |score, value| {
+            score * value.max(0.0).ln_1p() as Score
+        })
+    }
+}
+
+impl Clone for FunctionScoreQuery {
+    fn clone(&self) -> Self {
+        FunctionScoreQuery {
+            query: self.query.box_clone(),
+            field: self.field,
+            combiner: self.combiner.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for FunctionScoreQuery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FunctionScore(query={:?}, field={:?})",
+            self.query, self.field
+        )
+    }
+}
+
+impl Query for FunctionScoreQuery {
+    fn weight(&self, searcher: &Searcher, scoring_enabled: bool) -> crate::Result<Box<dyn Weight>> {
+        let inner_weight = self.query.weight(searcher, scoring_enabled)?;
+        Ok(if scoring_enabled {
+            Box::new(FunctionScoreWeight {
+                weight: inner_weight,
+                field: self.field,
+                combiner: self.combiner.clone(),
+            })
+        } else {
+            inner_weight
+        })
+    }
+
+    fn query_terms(&self, term_set: &mut BTreeSet<Term>) {
+        self.query.query_terms(term_set)
+    }
+}
+
+struct FunctionScoreWeight {
+    weight: Box<dyn Weight>,
+    field: Field,
+    combiner: ScoreCombiner,
+}
+
+impl FunctionScoreWeight {
+    fn function_scorer(
+        &self,
+        reader: &SegmentReader,
+        boost: Score,
+    ) -> crate::Result<FunctionScorer<Box<dyn Scorer>>> {
+        let underlying = self.weight.scorer(reader, boost)?;
+        let values = FastFieldValues::open(reader, self.field)?;
+        Ok(FunctionScorer {
+            underlying,
+            values,
+            combiner: self.combiner.clone(),
+        })
+    }
+}
+
+impl Weight for FunctionScoreWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> crate::Result<Box<dyn Scorer>> {
+        Ok(Box::new(self.function_scorer(reader, boost)?))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> crate::Result<Explanation> {
+        let mut scorer = self.function_scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(does_not_match(doc));
+        }
+        let value = scorer.values.get(doc);
+        let mut explanation = Explanation::new("FunctionScore", scorer.score());
+        explanation.add_context(format!("Fast field value: {}", value));
+        explanation.add_detail(self.weight.explain(reader, doc)?);
+        Ok(explanation)
+    }
+
+    fn count(&self, reader: &SegmentReader) -> crate::Result<u32> {
+        self.weight.count(reader)
+    }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        self.weight.estimate_cost(reader)
+    }
+}
+
+/// Fast field reader returning the value of a numerical fast field as a `f64`.
+enum FastFieldValues {
+    U64(FastFieldReader<u64>),
+    I64(FastFieldReader<i64>),
+    F64(FastFieldReader<f64>),
+    Date(FastFieldReader<DateTime>),
+}
+
+impl FastFieldValues {
+    fn open(reader: &SegmentReader, field: Field) -> crate::Result<FastFieldValues> {
+        let fast_fields = reader.fast_fields();
+        let field_entry = reader.schema().get_field_entry(field);
+        match field_entry.field_type() {
+            FieldType::U64(_) => Ok(FastFieldValues::U64(fast_fields.u64(field)?)),
+            FieldType::I64(_) => Ok(FastFieldValues::I64(fast_fields.i64(field)?)),
+            FieldType::F64(_) => Ok(FastFieldValues::F64(fast_fields.f64(field)?)),
+            FieldType::Date(_) => Ok(FastFieldValues::Date(fast_fields.date(field)?)),
+            _ => Err(TantivyError::SchemaError(format!(
+                "Field {:?} is not a numerical fast field.",
+                field_entry.name()
+            ))),
+        }
+    }
+
+    fn get(&self, doc: DocId) -> f64 {
+        match self {
+            FastFieldValues::U64(reader) => reader.get(doc) as f64,
+            FastFieldValues::I64(reader) => reader.get(doc) as f64,
+            FastFieldValues::F64(reader) => reader.get(doc),
+            FastFieldValues::Date(reader) => reader.get(doc).timestamp() as f64,
+        }
+    }
+}
+
+struct FunctionScorer<S: Scorer> {
+    underlying: S,
+    values: FastFieldValues,
+    combiner: ScoreCombiner,
+}
+
+impl<S: Scorer> DocSet for FunctionScorer<S> {
+    fn advance(&mut self) -> DocId {
+        self.underlying.advance()
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        self.underlying.seek(target)
+    }
+
+    fn fill_buffer(&mut self, buffer: &mut [DocId]) -> usize {
+        self.underlying.fill_buffer(buffer)
+    }
+
+    fn doc(&self) -> DocId {
+        self.underlying.doc()
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.underlying.size_hint()
+    }
+
+    fn count(&mut self, delete_bitset: &DeleteBitSet) -> u32 {
+        self.underlying.count(delete_bitset)
+    }
+
+    fn count_including_deleted(&mut self) -> u32 {
+        self.underlying.count_including_deleted()
+    }
+}
+
+impl<S: Scorer> Scorer for FunctionScorer<S> {
+    fn score(&mut self) -> Score {
+        let value = self.values.get(self.underlying.doc());
+        (self.combiner)(self.underlying.score(), value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FunctionScoreQuery;
+    use crate::collector::TopDocs;
+    use crate::query::{Query, TermQuery};
+    use crate::schema::{IndexRecordOption, Schema, FAST, STRING, TEXT};
+    use crate::{DocAddress, Index, Term};
+
+    #[test]
+    fn test_function_score_query_ranks_by_popularity() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let popularity = schema_builder.add_u64_field("popularity", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title=>"the diary", popularity=>3u64));
+        index_writer.add_document(doc!(title=>"the diary", popularity=>100u64));
+        index_writer.add_document(doc!(title=>"the diary", popularity=>10u64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query: Box<dyn Query> = Box::new(TermQuery::new(
+            Term::from_field_text(title, "diary"),
+            IndexRecordOption::WithFreqs,
+        ));
+        let relevance = searcher.search(&term_query, &TopDocs::with_limit(3))?;
+        assert!(relevance.windows(2).all(|docs| docs[0].0 == docs[1].0));
+        let query = FunctionScoreQuery::log1p_product(term_query.box_clone(), popularity);
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(3))?;
+        let ranked: Vec<DocAddress> = top_docs.iter().map(|(_, doc)| *doc).collect();
+        assert_eq!(
+            ranked,
+            vec![DocAddress(0, 1), DocAddress(0, 2), DocAddress(0, 0)]
+        );
+        let expected_score = relevance[0].0 * (101f64).ln() as f32;
+        assert!((top_docs[0].0 - expected_score).abs() < 1e-5);
+        assert_eq!(query.count(&searcher)?, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_score_query_custom_combiner() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let age = schema_builder.add_i64_field("age", FAST);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title=>"news", age=>30i64));
+        index_writer.add_document(doc!(title=>"news", age=>1i64));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(title, "news"),
+            IndexRecordOption::Basic,
+        );
+        let query = FunctionScoreQuery::new(Box::new(term_query), age, |_score, age| {
+            1.0 / (1.0 + age as f32)
+        });
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(2))?;
+        assert_eq!(top_docs[0], (0.5, DocAddress(0, 1)));
+        let explanation = query.explain(&searcher, DocAddress(0, 0))?;
+        assert!((explanation.value() - 1.0 / 31.0).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_function_score_query_requires_fast_field() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT);
+        let tag = schema_builder.add_text_field("tag", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(title=>"news", tag=>"a"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(title, "news"),
+            IndexRecordOption::Basic,
+        );
+        let query = FunctionScoreQuery::sum(Box::new(term_query), tag);
+        assert!(searcher.search(&query, &TopDocs::with_limit(1)).is_err());
+        Ok(())
+    }
+}
@@ -49,6 +49,32 @@ static LEV_BUILDER: Lazy<HashMap<(u8, bool), LevenshteinAutomatonBuilder>> = Laz
     lev_builder_cache
 });
 
+/// Upper bound of the Levenshtein distances accepted by fuzzy matching.
+pub(crate) const MAX_LEVENSHTEIN_DISTANCE: u8 = VALID_LEVENSHTEIN_DISTANCE_RANGE.end - 1;
+
+fn lev_builder(
+    distance: u8,
+    transposition_cost_one: bool,
+) -> crate::Result<&'static LevenshteinAutomatonBuilder> {
+    LEV_BUILDER
+        .get(&(distance, transposition_cost_one))
+        .ok_or_else(|| {
+            InvalidArgument(format!(
+                "Levenshtein distance of {} is not allowed. Choose a value in the {:?} range",
+                distance, VALID_LEVENSHTEIN_DISTANCE_RANGE
+            ))
+        })
+}
+
+/// Builds a DFA matching the terms within `distance` edits of `text`.
+pub(crate) fn levenshtein_dfa(
+    text: &str,
+    distance: u8,
+    transposition_cost_one: bool,
+) -> crate::Result<DFA> {
+    Ok(lev_builder(distance, transposition_cost_one)?.build_dfa(text))
+}
+
 /// A Fuzzy Query matches all of the documents
 /// containing a specific term that is within
 /// Levenshtein distance
@@ -128,25 +154,16 @@ impl FuzzyTermQuery {
     }
 
     fn specialized_weight(&self) -> crate::Result<AutomatonWeight<DFAWrapper>> {
-        // LEV_BUILDER is a HashMap, whose `get` method returns an Option
-        match LEV_BUILDER.get(&(self.distance, false)) {
-            // Unwrap the option and build the Ok(AutomatonWeight)
-            Some(automaton_builder) => {
-                let automaton = if self.prefix {
-                    automaton_builder.build_prefix_dfa(self.term.text())
-                } else {
-                    automaton_builder.build_dfa(self.term.text())
-                };
-                Ok(AutomatonWeight::new(
-                    self.term.field(),
-                    DFAWrapper(automaton),
-                ))
-            }
-            None => Err(InvalidArgument(format!(
-                "Levenshtein distance of {} is not allowed. Choose a value in the {:?} range",
-                self.distance, VALID_LEVENSHTEIN_DISTANCE_RANGE
-            ))),
-        }
+        let automaton_builder = lev_builder(self.distance, self.transposition_cost_one)?;
+        let automaton = if self.prefix {
+            automaton_builder.build_prefix_dfa(self.term.text())
+        } else {
+            automaton_builder.build_dfa(self.term.text())
+        };
+        Ok(AutomatonWeight::new(
+            self.term.field(),
+            DFAWrapper(automaton),
+        ))
     }
 }
 
@@ -164,11 +181,11 @@ impl Query for FuzzyTermQuery {
 mod test {
     use super::FuzzyTermQuery;
     use crate::assert_nearly_equals;
-    use crate::collector::TopDocs;
+    use crate::collector::{DocSetCollector, TopDocs};
     use crate::schema::Schema;
     use crate::schema::TEXT;
-    use crate::Index;
     use crate::Term;
+    use crate::{DocAddress, Index};
 
     #[test]
     pub fn test_fuzzy_term() {
@@ -226,4 +243,39 @@ mod test {
             assert_nearly_equals!(1.0, score);
         }
     }
+
+    #[test]
+    fn test_fuzzy_term_transposition() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text => "colar"));
+        index_writer.add_document(doc!(text => "cloor"));
+        index_writer.add_document(doc!(text => "coloring"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term = Term::from_field_text(text, "color");
+        let matching_docs = |transposition_cost_one: bool| -> crate::Result<Vec<DocAddress>> {
+            let fuzzy_query = FuzzyTermQuery::new(term.clone(), 1, transposition_cost_one);
+            let mut docs: Vec<DocAddress> = searcher
+                .search(&fuzzy_query, &DocSetCollector)?
+                .into_iter()
+                .collect();
+            docs.sort();
+            Ok(docs)
+        };
+        // "cloor" is 1 transposition, or 2 substitutions away from "color".
+        assert_eq!(
+            matching_docs(true)?,
+            vec![DocAddress(0, 0), DocAddress(0, 1)]
+        );
+        assert_eq!(matching_docs(false)?, vec![DocAddress(0, 0)]);
+        let too_far = FuzzyTermQuery::new(term, 3, true);
+        assert!(matches!(
+            searcher.search(&too_far, &DocSetCollector),
+            Err(crate::TantivyError::InvalidArgument(_))
+        ));
+        Ok(())
+    }
 }
@@ -0,0 +1,158 @@
+use crate::core::Searcher;
+use crate::docset::{DocSet, TERMINATED};
+use crate::fastfield::DeleteBitSet;
+use crate::postings::SegmentPostings;
+use crate::schema::IndexRecordOption;
+use crate::{DocId, Term};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Postings of a term in one segment, with the base offset of the segment.
+struct SegmentCursor {
+    base: DocId,
+    postings: SegmentPostings,
+    delete_bitset: Option<DeleteBitSet>,
+}
+
+impl SegmentCursor {
+    /// Returns the first global doc id at or after the current position
+    /// that is not deleted.
+    fn skip_deleted(&mut self) -> DocId {
+        let mut doc = self.postings.doc();
+        if let Some(delete_bitset) = self.delete_bitset.as_ref() {
+            while doc != TERMINATED && delete_bitset.is_deleted(doc) {
+                doc = self.postings.advance();
+            }
+        }
+        if doc == TERMINATED {
+            return TERMINATED;
+        }
+        self.base + doc
+    }
+}
+
+/// `DocSet` over the documents containing a given term, across all of the
+/// segments of a `Searcher`.
+///
+/// Doc ids are global: the doc ids of a segment are shifted by the sum of the
+/// `max_doc` of the segments preceding it in `Searcher::segment_readers()`.
+///
+/// The per-segment postings are merged lazily with a small heap,
+/// so no combined posting list is ever materialized.
+/// Deleted documents are skipped.
+pub struct GlobalTermDocSet {
+    cursors: Vec<SegmentCursor>,
+    // Next global doc id of each segment cursor, except the one
+    // that produced `doc`.
+    heap: BinaryHeap<Reverse<(DocId, usize)>>,
+    doc: DocId,
+}
+
+impl GlobalTermDocSet {
+    /// Creates a `GlobalTermDocSet` for `term`, positioned on its first document.
+    pub fn new(searcher: &Searcher, term: &Term) -> crate::Result<GlobalTermDocSet> {
+        let mut cursors = Vec::with_capacity(searcher.segment_readers().len());
+        let mut base = 0u32;
+        for segment_reader in searcher.segment_readers() {
+            let inverted_index = segment_reader.inverted_index(term.field())?;
+            if let Some(postings) = inverted_index.read_postings(term, IndexRecordOption::Basic)? {
+                cursors.push(SegmentCursor {
+                    base,
+                    postings,
+                    delete_bitset: segment_reader.delete_bitset().cloned(),
+                });
+            }
+            base += segment_reader.max_doc();
+        }
+        let heap = cursors
+            .iter_mut()
+            .enumerate()
+            .map(|(ord, cursor)| (cursor.skip_deleted(), ord))
+            .filter(|&(doc, _)| doc != TERMINATED)
+            .map(Reverse)
+            .collect();
+        let mut global_term_docset = GlobalTermDocSet {
+            cursors,
+            heap,
+            doc: TERMINATED,
+        };
+        global_term_docset.advance();
+        Ok(global_term_docset)
+    }
+}
+
+impl DocSet for GlobalTermDocSet {
+    fn advance(&mut self) -> DocId {
+        let Reverse((doc, ord)) = match self.heap.pop() {
+            Some(head) => head,
+            None => {
+                self.doc = TERMINATED;
+                return TERMINATED;
+            }
+        };
+        self.doc = doc;
+        let cursor = &mut self.cursors[ord];
+        cursor.postings.advance();
+        let next_doc = cursor.skip_deleted();
+        if next_doc != TERMINATED {
+            self.heap.push(Reverse((next_doc, ord)));
+        }
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.cursors
+            .iter()
+            .map(|cursor| cursor.postings.size_hint())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GlobalTermDocSet;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::schema::{Schema, STORED, TEXT};
+    use crate::{DocId, Index, Term};
+
+    #[test]
+    fn test_global_term_docset_three_segments() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        let segments: [&[&str]; 3] = [&["a", "b", "a"], &["b", "b", "a", "a"], &["a", "c"]];
+        for docs in segments.iter() {
+            for &doc in docs.iter() {
+                index_writer.add_document(doc!(text => doc));
+            }
+            index_writer.commit()?;
+        }
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 3);
+        let mut expected: Vec<DocId> = Vec::new();
+        let mut base = 0;
+        for segment_reader in searcher.segment_readers() {
+            let store_reader = segment_reader.get_store_reader()?;
+            for doc in 0..segment_reader.max_doc() {
+                if store_reader.get(doc)?.get_first(text).unwrap().text() == Some("a") {
+                    expected.push(base + doc);
+                }
+            }
+            base += segment_reader.max_doc();
+        }
+        assert_eq!(expected.len(), 5);
+        let mut docset = GlobalTermDocSet::new(&searcher, &Term::from_field_text(text, "a"))?;
+        let mut docs = Vec::new();
+        while docset.doc() != TERMINATED {
+            docs.push(docset.doc());
+            docset.advance();
+        }
+        assert_eq!(docs, expected);
+        Ok(())
+    }
+}
@@ -45,6 +45,35 @@ pub fn intersect_scorers(mut scorers: Vec<Box<dyn Scorer>>) -> Box<dyn Scorer> {
     })
 }
 
+/// Returns the number of documents that belong to both `DocSet`s and
+/// for which `is_alive` returns true, without materializing the intersection.
+///
+/// The `DocSet` with the lowest `size_hint` drives the iteration:
+/// the other one is only moved forward with `.seek(...)`.
+pub(crate) fn intersection_count<TDocSet: DocSet>(
+    mut left: TDocSet,
+    mut right: TDocSet,
+    is_alive: impl Fn(DocId) -> bool,
+) -> u32 {
+    if right.size_hint() < left.size_hint() {
+        std::mem::swap(&mut left, &mut right);
+    }
+    let mut count = 0u32;
+    let mut candidate = left.doc();
+    while candidate != TERMINATED {
+        let right_doc = right.seek(candidate);
+        if right_doc == candidate {
+            if is_alive(candidate) {
+                count += 1;
+            }
+            candidate = left.advance();
+        } else {
+            candidate = left.seek(right_doc);
+        }
+    }
+    count
+}
+
 /// Creates a `DocSet` that iterate through the intersection of two or more `DocSet`s.
 pub struct Intersection<TDocSet: DocSet, TOtherDocSet: DocSet = Box<dyn Scorer>> {
     left: TDocSet,
@@ -161,10 +190,77 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::Intersection;
+    use super::{intersection_count, Intersection};
     use crate::docset::{DocSet, TERMINATED};
     use crate::postings::tests::test_skip_against_unoptimized;
     use crate::query::VecDocSet;
+    use crate::DocId;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Counts the calls to `.advance()` and `.seek(...)`.
+    struct CountingDocSet {
+        docset: VecDocSet,
+        num_advances: Arc<AtomicUsize>,
+        num_seeks: Arc<AtomicUsize>,
+    }
+
+    impl CountingDocSet {
+        fn new(doc_ids: Vec<DocId>) -> CountingDocSet {
+            CountingDocSet {
+                docset: VecDocSet::from(doc_ids),
+                num_advances: Arc::default(),
+                num_seeks: Arc::default(),
+            }
+        }
+    }
+
+    impl DocSet for CountingDocSet {
+        fn advance(&mut self) -> DocId {
+            self.num_advances.fetch_add(1, Ordering::SeqCst);
+            self.docset.advance()
+        }
+
+        fn seek(&mut self, target: DocId) -> DocId {
+            self.num_seeks.fetch_add(1, Ordering::SeqCst);
+            self.docset.seek(target)
+        }
+
+        fn doc(&self) -> DocId {
+            self.docset.doc()
+        }
+
+        fn size_hint(&self) -> u32 {
+            self.docset.size_hint()
+        }
+    }
+
+    #[test]
+    fn test_intersection_count_seeks_in_frequent_docset() {
+        let rare = CountingDocSet::new(vec![3, 500, 1_000, 1_002]);
+        let frequent = CountingDocSet::new((0..2_000).filter(|doc| doc % 3 != 2).collect());
+        let (rare_advances, rare_seeks) = (rare.num_advances.clone(), rare.num_seeks.clone());
+        let (frequent_advances, frequent_seeks) =
+            (frequent.num_advances.clone(), frequent.num_seeks.clone());
+        // The order of the arguments does not matter.
+        assert_eq!(intersection_count(frequent, rare, |_| true), 3);
+        assert_eq!(frequent_advances.load(Ordering::SeqCst), 0);
+        assert_eq!(frequent_seeks.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            rare_advances.load(Ordering::SeqCst) + rare_seeks.load(Ordering::SeqCst),
+            4
+        );
+    }
+
+    #[test]
+    fn test_intersection_count_is_alive() {
+        let left = VecDocSet::from(vec![1, 3, 9, 10]);
+        let right = VecDocSet::from(vec![3, 4, 9, 10, 18]);
+        assert_eq!(intersection_count(left, right, |doc| doc != 9), 2);
+        let left = VecDocSet::from(vec![1, 3]);
+        let right = VecDocSet::from(vec![4, 9]);
+        assert_eq!(intersection_count(left, right, |_| true), 0);
+    }
 
     #[test]
     fn test_intersection() {
@@ -1,18 +1,23 @@
 /*! Query Module */
 
 mod all_query;
+mod all_terms_in_field;
 mod automaton_weight;
 mod bitset;
 mod bm25;
 mod boolean_query;
 mod boost_query;
+mod const_score_query;
 mod empty_query;
 mod exclude;
 mod explanation;
+mod function_score_query;
 mod fuzzy_query;
+mod global_term_docset;
 mod intersection;
 mod phrase_query;
 mod query;
+mod query_cost;
 mod query_parser;
 mod range_query;
 mod regex_query;
@@ -20,6 +25,7 @@ mod reqopt_scorer;
 mod scorer;
 mod term_query;
 mod union;
+mod union_scorer;
 mod weight;
 
 #[cfg(test)]
@@ -27,26 +33,32 @@ mod vec_docset;
 
 pub(crate) mod score_combiner;
 pub(crate) use self::bm25::BM25Weight;
+pub(crate) use self::intersection::intersection_count;
 pub use self::intersection::Intersection;
 pub use self::union::Union;
+pub use self::union_scorer::UnionScorer;
 
 #[cfg(test)]
 pub use self::vec_docset::VecDocSet;
 
 pub use self::all_query::{AllQuery, AllScorer, AllWeight};
+pub use self::all_terms_in_field::AllTermsInFieldDocSet;
 pub use self::automaton_weight::AutomatonWeight;
 pub use self::bitset::BitSetDocSet;
 pub use self::boolean_query::BooleanQuery;
 pub use self::boost_query::BoostQuery;
+pub use self::const_score_query::ConstScoreQuery;
 pub use self::empty_query::{EmptyQuery, EmptyScorer, EmptyWeight};
 pub use self::exclude::Exclude;
 pub use self::explanation::Explanation;
-#[cfg(test)]
-pub(crate) use self::fuzzy_query::DFAWrapper;
+pub use self::function_score_query::FunctionScoreQuery;
 pub use self::fuzzy_query::FuzzyTermQuery;
+pub(crate) use self::fuzzy_query::{levenshtein_dfa, DFAWrapper, MAX_LEVENSHTEIN_DISTANCE};
+pub use self::global_term_docset::GlobalTermDocSet;
 pub use self::intersection::intersect_scorers;
 pub use self::phrase_query::PhraseQuery;
 pub use self::query::{Query, QueryClone};
+pub use self::query_cost::{QueryCost, MAX_ESTIMATED_TERM_EXPANSION};
 pub use self::query_parser::QueryParser;
 pub use self::query_parser::QueryParserError;
 pub use self::range_query::RangeQuery;
@@ -246,4 +246,40 @@ pub mod tests {
         assert!(test_query(vec![(0, "a"), (2, "d")]).is_empty());
         assert_eq!(test_query(vec![(1, "a"), (3, "c")]), vec![0]);
     }
+
+    #[test]
+    pub fn test_phrase_query_slop() {
+        let index = create_index(&[
+            "the quick brown fox",
+            "the brown quick fox",
+            "the quick red brown fox",
+            "the quick big red brown fox",
+            "a b b x c",
+        ]);
+        let text_field = index.schema().get_field("text").unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let test_query = |texts: Vec<&str>, slop: u32| {
+            let terms: Vec<Term> = texts
+                .iter()
+                .map(|text| Term::from_field_text(text_field, text))
+                .collect();
+            let mut phrase_query = PhraseQuery::new(terms);
+            phrase_query.set_slop(slop);
+            searcher
+                .search(&phrase_query, &TEST_COLLECTOR_WITHOUT_SCORE)
+                .expect("search should succeed")
+                .docs()
+                .iter()
+                .map(|doc_address| doc_address.1)
+                .collect::<Vec<DocId>>()
+        };
+        assert_eq!(test_query(vec!["quick", "brown"], 0), vec![0]);
+        assert_eq!(test_query(vec!["quick", "brown"], 1), vec![0, 2]);
+        assert_eq!(test_query(vec!["quick", "brown"], 2), vec![0, 2, 3]);
+        assert_eq!(test_query(vec!["quick", "brown", "fox"], 1), vec![0, 2]);
+        assert_eq!(test_query(vec!["brown", "quick"], 2), vec![1]);
+        // a@0 -> b@2 -> c@4: `b@1` must not hide `b@2` from the next term.
+        assert!(test_query(vec!["a", "b", "c"], 0).is_empty());
+        assert_eq!(test_query(vec!["a", "b", "c"], 1), vec![4]);
+    }
 }
@@ -18,13 +18,19 @@ use std::collections::BTreeSet;
 ///
 /// **This is my favorite part of the job.**
 ///
+/// A slop can be set with [`.set_slop(...)`](#method.set_slop) to
+/// tolerate up to `slop` extra positions between consecutive terms.
+/// The terms still need to appear in order.
+///
 /// Using a `PhraseQuery` on a field requires positions
 /// to be indexed for this field.
 ///
+/// The phrase is scored using the default BM25 parameters.
 #[derive(Clone, Debug)]
 pub struct PhraseQuery {
     field: Field,
     phrase_terms: Vec<(usize, Term)>,
+    slop: u32,
 }
 
 impl PhraseQuery {
@@ -55,9 +61,23 @@ impl PhraseQuery {
         PhraseQuery {
             field,
             phrase_terms: terms,
+            slop: 0,
         }
     }
 
+    /// Sets the slop of the phrase query, i.e. the maximum number of
+    /// extra positions allowed between two consecutive terms.
+    ///
+    /// The default slop is 0, meaning that the terms must be adjacent.
+    pub fn set_slop(&mut self, value: u32) {
+        self.slop = value;
+    }
+
+    /// Slop of the phrase query.
+    pub fn slop(&self) -> u32 {
+        self.slop
+    }
+
     /// The `Field` this `PhraseQuery` is targeting.
     pub fn field(&self) -> Field {
         self.field
@@ -99,6 +119,7 @@ impl PhraseQuery {
         Ok(PhraseWeight::new(
             self.phrase_terms.clone(),
             bm25_weight,
+            self.slop,
             scoring_enabled,
         ))
     }
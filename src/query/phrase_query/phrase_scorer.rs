@@ -47,9 +47,11 @@ pub struct PhraseScorer<TPostings: Postings> {
     num_terms: usize,
     left: Vec<u32>,
     right: Vec<u32>,
+    slop_buffer: Vec<u32>,
     phrase_count: u32,
     fieldnorm_reader: FieldNormReader,
     similarity_weight: BM25Weight,
+    slop: u32,
     score_needed: bool,
 }
 
@@ -130,11 +132,34 @@ fn intersection(left: &mut [u32], right: &[u32]) -> usize {
     count
 }
 
+/// Intersect twos sorted arrays `left` and `right`, tolerating a gap of
+/// up to `slop` positions, and outputs the matched `right` positions in `output`.
+///
+/// A value of `right` matches if some value of `left` is in
+/// `[right - slop, right]`. A value of `left` may match several values of `right`.
+fn intersection_with_slop(left: &[u32], right: &[u32], slop: u32, output: &mut Vec<u32>) {
+    output.clear();
+    let mut left_i = 0;
+    for &right_val in right {
+        let lower_bound = right_val.saturating_sub(slop);
+        while left_i < left.len() && left[left_i] < lower_bound {
+            left_i += 1;
+        }
+        if left_i == left.len() {
+            break;
+        }
+        if left[left_i] <= right_val {
+            output.push(right_val);
+        }
+    }
+}
+
 impl<TPostings: Postings> PhraseScorer<TPostings> {
     pub fn new(
         term_postings: Vec<(usize, TPostings)>,
         similarity_weight: BM25Weight,
         fieldnorm_reader: FieldNormReader,
+        slop: u32,
         score_needed: bool,
     ) -> PhraseScorer<TPostings> {
         let max_offset = term_postings
@@ -154,9 +179,11 @@ impl<TPostings: Postings> PhraseScorer<TPostings> {
             num_terms: num_docsets,
             left: Vec::with_capacity(100),
             right: Vec::with_capacity(100),
+            slop_buffer: Vec::new(),
             phrase_count: 0u32,
             similarity_weight,
             fieldnorm_reader,
+            slop,
             score_needed,
         };
         if scorer.doc() != TERMINATED && !scorer.phrase_match() {
@@ -180,51 +207,62 @@ impl<TPostings: Postings> PhraseScorer<TPostings> {
     }
 
     fn phrase_exists(&mut self) -> bool {
-        self.intersection_docset
-            .docset_mut_specialized(0)
-            .positions(&mut self.left);
-        let mut intersection_len = self.left.len();
-        for i in 1..self.num_terms - 1 {
-            {
-                self.intersection_docset
-                    .docset_mut_specialized(i)
-                    .positions(&mut self.right);
-            }
-            intersection_len = intersection(&mut self.left[..intersection_len], &self.right[..]);
-            if intersection_len == 0 {
-                return false;
-            }
+        let intersection_len = self.compute_phrase_match();
+        if self.slop > 0 {
+            return intersection_len > 0;
         }
-
-        self.intersection_docset
-            .docset_mut_specialized(self.num_terms - 1)
-            .positions(&mut self.right);
-        intersection_exists(&self.left[..intersection_len], &self.right[..])
+        intersection_len > 0 && intersection_exists(&self.left[..intersection_len], &self.right[..])
     }
 
     fn compute_phrase_count(&mut self) -> u32 {
-        {
-            self.intersection_docset
-                .docset_mut_specialized(0)
-                .positions(&mut self.left);
+        let intersection_len = self.compute_phrase_match();
+        if self.slop > 0 || intersection_len == 0 {
+            return intersection_len as u32;
         }
+        intersection_count(&self.left[..intersection_len], &self.right[..]) as u32
+    }
+
+    /// Intersects the positions of the terms, leaving the candidates in `self.left`,
+    /// and returns their number.
+    ///
+    /// Without slop, the positions of the last term are only loaded in `self.right`,
+    /// and are left for the caller to intersect.
+    fn compute_phrase_match(&mut self) -> usize {
+        self.intersection_docset
+            .docset_mut_specialized(0)
+            .positions(&mut self.left);
         let mut intersection_len = self.left.len();
-        for i in 1..self.num_terms - 1 {
-            {
-                self.intersection_docset
-                    .docset_mut_specialized(i)
-                    .positions(&mut self.right);
-            }
-            intersection_len = intersection(&mut self.left[..intersection_len], &self.right[..]);
+        let end_term = if self.slop > 0 {
+            self.num_terms
+        } else {
+            self.num_terms - 1
+        };
+        for i in 1..end_term {
+            self.intersection_docset
+                .docset_mut_specialized(i)
+                .positions(&mut self.right);
+            intersection_len = if self.slop > 0 {
+                intersection_with_slop(
+                    &self.left[..intersection_len],
+                    &self.right[..],
+                    self.slop,
+                    &mut self.slop_buffer,
+                );
+                std::mem::swap(&mut self.left, &mut self.slop_buffer);
+                self.left.len()
+            } else {
+                intersection(&mut self.left[..intersection_len], &self.right[..])
+            };
             if intersection_len == 0 {
-                return 0u32;
+                return 0;
             }
         }
-
-        self.intersection_docset
-            .docset_mut_specialized(self.num_terms - 1)
-            .positions(&mut self.right);
-        intersection_count(&self.left[..intersection_len], &self.right[..]) as u32
+        if self.slop == 0 {
+            self.intersection_docset
+                .docset_mut_specialized(self.num_terms - 1)
+                .positions(&mut self.right);
+        }
+        intersection_len
     }
 }
 
@@ -267,7 +305,7 @@ impl<TPostings: Postings> Scorer for PhraseScorer<TPostings> {
 
 #[cfg(test)]
 mod tests {
-    use super::{intersection, intersection_count};
+    use super::{intersection, intersection_count, intersection_with_slop};
 
     fn test_intersection_sym(left: &[u32], right: &[u32], expected: &[u32]) {
         test_intersection_aux(left, right, expected);
@@ -282,6 +320,24 @@ mod tests {
         assert_eq!(&left_mut[..count], expected);
     }
 
+    #[test]
+    fn test_intersection_with_slop() {
+        let test = |left: &[u32], right: &[u32], slop: u32, expected: &[u32]| {
+            let mut output = Vec::new();
+            intersection_with_slop(left, right, slop, &mut output);
+            assert_eq!(&output[..], expected);
+        };
+        test(&[1], &[1], 0, &[1]);
+        test(&[1], &[2], 0, &[]);
+        test(&[1], &[2], 1, &[2]);
+        test(&[3], &[2], 1, &[]);
+        test(&[1, 2], &[3], 2, &[3]);
+        test(&[1, 4, 8], &[2, 5, 12], 1, &[2, 5]);
+        test(&[], &[2], 3, &[]);
+        test(&[2], &[2, 3], 1, &[2, 3]);
+        test(&[1, 3], &[2, 3, 4], 1, &[2, 3, 4]);
+    }
+
     #[test]
     fn test_intersection() {
         test_intersection_sym(&[1], &[1], &[1]);
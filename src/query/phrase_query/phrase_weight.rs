@@ -6,7 +6,7 @@ use crate::query::bm25::BM25Weight;
 use crate::query::explanation::does_not_match;
 use crate::query::Scorer;
 use crate::query::Weight;
-use crate::query::{EmptyScorer, Explanation};
+use crate::query::{EmptyScorer, Explanation, QueryCost};
 use crate::schema::IndexRecordOption;
 use crate::schema::Term;
 use crate::Score;
@@ -15,6 +15,7 @@ use crate::{DocId, DocSet};
 pub struct PhraseWeight {
     phrase_terms: Vec<(usize, Term)>,
     similarity_weight: BM25Weight,
+    slop: u32,
     score_needed: bool,
 }
 
@@ -23,11 +24,13 @@ impl PhraseWeight {
     pub fn new(
         phrase_terms: Vec<(usize, Term)>,
         similarity_weight: BM25Weight,
+        slop: u32,
         score_needed: bool,
     ) -> PhraseWeight {
         PhraseWeight {
             phrase_terms,
             similarity_weight,
+            slop,
             score_needed,
         }
     }
@@ -60,6 +63,7 @@ impl PhraseWeight {
                 term_postings_list,
                 similarity_weight,
                 fieldnorm_reader,
+                self.slop,
                 self.score_needed,
             )))
         } else {
@@ -78,6 +82,7 @@ impl PhraseWeight {
                 term_postings_list,
                 similarity_weight,
                 fieldnorm_reader,
+                self.slop,
                 self.score_needed,
             )))
         }
@@ -109,6 +114,16 @@ impl Weight for PhraseWeight {
         explanation.add_detail(self.similarity_weight.explain(fieldnorm_id, phrase_count));
         Ok(explanation)
     }
+
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        let mut cost: Option<QueryCost> = None;
+        for (_, term) in &self.phrase_terms {
+            let doc_freq = reader.inverted_index(term.field())?.doc_freq(term)?;
+            let term_cost = QueryCost::new(u64::from(doc_freq), 1);
+            cost = Some(cost.map_or(term_cost, |cost| cost.intersect(term_cost)));
+        }
+        Ok(cost.unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
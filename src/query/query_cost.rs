@@ -0,0 +1,57 @@
+use std::ops::{Add, AddAssign};
+
+/// Maximum number of terms an automaton based query (`RegexQuery`,
+/// `FuzzyTermQuery`) expands to while estimating its cost on a segment.
+///
+/// Past this cap, the query is assumed to scan the entire segment.
+pub const MAX_ESTIMATED_TERM_EXPANSION: u64 = 10_000;
+
+/// `QueryCost` is a rough estimate of the work required to run a query.
+///
+/// It is computed from the per-segment doc freq statistics of the terms
+/// involved in the query, without decoding any posting list.
+/// See [`Searcher::estimate_cost`](../struct.Searcher.html#method.estimate_cost).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueryCost {
+    /// Estimated number of documents that will be scanned.
+    pub num_docs: u64,
+    /// Number of terms the query expands to.
+    pub num_terms: u64,
+}
+
+impl QueryCost {
+    /// Creates a new `QueryCost`.
+    pub fn new(num_docs: u64, num_terms: u64) -> QueryCost {
+        QueryCost {
+            num_docs,
+            num_terms,
+        }
+    }
+
+    /// Cost of a query intersecting two sets of documents.
+    ///
+    /// The number of documents scanned is bounded by the rarest of the two.
+    pub fn intersect(self, other: QueryCost) -> QueryCost {
+        QueryCost {
+            num_docs: self.num_docs.min(other.num_docs),
+            num_terms: self.num_terms + other.num_terms,
+        }
+    }
+}
+
+impl Add for QueryCost {
+    type Output = QueryCost;
+
+    fn add(self, other: QueryCost) -> QueryCost {
+        QueryCost {
+            num_docs: self.num_docs + other.num_docs,
+            num_terms: self.num_terms + other.num_terms,
+        }
+    }
+}
+
+impl AddAssign for QueryCost {
+    fn add_assign(&mut self, other: QueryCost) {
+        *self = *self + other;
+    }
+}
@@ -17,7 +17,7 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::num::{ParseFloatError, ParseIntError};
 use std::ops::Bound;
-use std::str::FromStr;
+use std::str::{FromStr, ParseBoolError};
 use tantivy_query_grammar::{UserInputAST, UserInputBound, UserInputLeaf};
 
 /// Possible error that may happen when parsing a query.
@@ -42,6 +42,10 @@ pub enum QueryParserError {
     /// is not a f64.
     #[error("Invalid query: Only excluding terms given")]
     ExpectedFloat(ParseFloatError),
+    /// The query contains a term for a `bool`-field, but the value
+    /// is neither `true` nor `false`.
+    #[error("Expected a bool value: '{0:?}'")]
+    ExpectedBool(ParseBoolError),
     /// It is forbidden queries that are only "excluding". (e.g. -title:pop)
     #[error("Invalid query: Only excluding terms given")]
     AllButQueryForbidden,
@@ -82,6 +86,12 @@ impl From<ParseFloatError> for QueryParserError {
     }
 }
 
+impl From<ParseBoolError> for QueryParserError {
+    fn from(err: ParseBoolError) -> QueryParserError {
+        QueryParserError::ExpectedBool(err)
+    }
+}
+
 impl From<chrono::ParseError> for QueryParserError {
     fn from(err: chrono::ParseError) -> QueryParserError {
         QueryParserError::DateFormatError(err)
@@ -168,6 +178,10 @@ fn trim_ast(logical_ast: LogicalAST) -> Option<LogicalAST> {
 /// It is also possible to define a boost for a some specific field, at the query parser level.
 /// (See [`set_boost(...)`](#method.set_field_boost) ). Typically you may want to boost a title
 /// field.
+///
+/// The resulting queries are scored using the default BM25 parameters. Custom parameters
+/// can only be set by building a [`TermQuery`](struct.TermQuery.html#method.with_bm25_params)
+/// directly.
 #[derive(Clone)]
 pub struct QueryParser {
     schema: Schema,
@@ -312,6 +326,11 @@ impl QueryParser {
                 let term = Term::from_field_u64(field, val);
                 Ok(vec![(0, term)])
             }
+            FieldType::Bool(_) => {
+                let val: bool = bool::from_str(phrase)?;
+                let term = Term::from_field_bool(field, val);
+                Ok(vec![(0, term)])
+            }
             FieldType::Str(ref str_options) => {
                 if let Some(option) = str_options.get_indexing_options() {
                     let tokenizer =
@@ -608,6 +627,7 @@ mod test {
         schema_builder.add_facet_field("facet");
         schema_builder.add_bytes_field("bytes", INDEXED);
         schema_builder.add_bytes_field("bytes_not_indexed", STORED);
+        schema_builder.add_bool_field("flag", INDEXED);
         schema_builder.build()
     }
 
@@ -1006,6 +1026,17 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_query_parser_expected_bool() {
+        let query_parser = make_query_parser();
+        assert_matches!(
+            query_parser.parse_query("flag:yes"),
+            Err(QueryParserError::ExpectedBool(_))
+        );
+        assert!(query_parser.parse_query("flag:true").is_ok());
+        assert!(query_parser.parse_query("flag:false").is_ok());
+    }
+
     #[test]
     pub fn test_query_parser_expected_date() {
         let query_parser = make_query_parser();
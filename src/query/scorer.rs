@@ -38,6 +38,12 @@ impl<TDocSet: DocSet> ConstScorer<TDocSet> {
     pub fn new(docset: TDocSet, score: Score) -> ConstScorer<TDocSet> {
         ConstScorer { docset, score }
     }
+
+    /// Returns the underlying `DocSet`.
+    #[cfg(test)]
+    pub(crate) fn into_inner(self) -> TDocSet {
+        self.docset
+    }
 }
 
 impl<TDocSet: DocSet> From<TDocSet> for ConstScorer<TDocSet> {
@@ -13,7 +13,9 @@ mod tests {
     use crate::docset::DocSet;
     use crate::postings::compression::COMPRESSION_BLOCK_SIZE;
     use crate::query::{Query, QueryParser, Scorer, TermQuery};
-    use crate::schema::{Field, IndexRecordOption, Schema, STRING, TEXT};
+    use crate::schema::{
+        Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, STRING, TEXT,
+    };
     use crate::{assert_nearly_equals, DocAddress};
     use crate::{Index, Term, TERMINATED};
 
@@ -42,6 +44,38 @@ mod tests {
         assert_nearly_equals!(term_scorer.score(), 0.28768212);
     }
 
+    #[test]
+    fn test_term_query_no_freq_scores_as_term_freq_one() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default().set_index_option(IndexRecordOption::Basic),
+        );
+        let text_field = schema_builder.add_text_field("text", text_options);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for doc in 0..300 {
+            if doc == 200 {
+                index_writer.add_document(doc!(text_field => "a"));
+            } else {
+                index_writer.add_document(doc!(text_field => "a b c d"));
+            }
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        for &requested_option in &[IndexRecordOption::Basic, IndexRecordOption::WithFreqs] {
+            let term_query =
+                TermQuery::new(Term::from_field_text(text_field, "a"), requested_option);
+            let top_docs = searcher.search(&term_query, &TopDocs::with_limit(3))?;
+            assert_eq!(top_docs.len(), 3);
+            assert_eq!(top_docs[0].1, DocAddress(0, 200));
+            let long_doc_score = top_docs[1].0;
+            assert!(long_doc_score > 0.0);
+            assert!(top_docs[0].0 > long_doc_score);
+            assert_nearly_equals!(top_docs[2].0, long_doc_score);
+        }
+        Ok(())
+    }
+
     #[test]
     pub fn test_term_query_multiple_of_block_len() -> crate::Result<()> {
         let mut schema_builder = Schema::builder();
@@ -215,4 +249,61 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_term_query_bm25_params() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(text_field=>"a b c d e f g h"));
+        index_writer.add_document(doc!(text_field=>"a b"));
+        index_writer.add_document(doc!(text_field=>"c d"));
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        let term_a = Term::from_field_text(text_field, "a");
+        let scores = |term_query: TermQuery| -> crate::Result<Vec<(f32, DocAddress)>> {
+            searcher.search(&term_query, &TopDocs::with_limit(2))
+        };
+        let default_scores = scores(TermQuery::new(term_a.clone(), IndexRecordOption::WithFreqs))?;
+        assert_eq!(default_scores[0].1, DocAddress(0, 1));
+        assert!(default_scores[0].0 > default_scores[1].0);
+        let no_length_norm_scores = scores(
+            TermQuery::new(term_a.clone(), IndexRecordOption::WithFreqs).with_bm25_params(1.2, 0.0),
+        )?;
+        assert_nearly_equals!(no_length_norm_scores[0].0, no_length_norm_scores[1].0);
+        let explicit_default_scores = scores(
+            TermQuery::new(term_a, IndexRecordOption::WithFreqs).with_bm25_params(1.2, 0.75),
+        )?;
+        assert_eq!(explicit_default_scores, default_scores);
+        Ok(())
+    }
+
+    fn search_with_bm25_params(k1: f32, b: f32) {
+        let mut schema_builder = Schema::builder();
+        let text_field = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests().unwrap();
+        index_writer.add_document(doc!(text_field=>"a b"));
+        index_writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let term_query = TermQuery::new(
+            Term::from_field_text(text_field, "a"),
+            IndexRecordOption::WithFreqs,
+        )
+        .with_bm25_params(k1, b);
+        let _ = searcher.search(&term_query, &TopDocs::with_limit(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "k1 must be non-negative")]
+    fn test_term_query_bm25_params_negative_k1() {
+        search_with_bm25_params(-1.0, 0.75);
+    }
+
+    #[test]
+    #[should_panic(expected = "b must be within [0, 1]")]
+    fn test_term_query_bm25_params_invalid_b() {
+        search_with_bm25_params(1.2, 1.5);
+    }
 }
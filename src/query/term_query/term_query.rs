@@ -2,7 +2,8 @@ use super::term_weight::TermWeight;
 use crate::query::bm25::BM25Weight;
 use crate::query::Weight;
 use crate::query::{Explanation, Query};
-use crate::schema::IndexRecordOption;
+use crate::schema::{Field, IndexRecordOption};
+use crate::Score;
 use crate::Searcher;
 use crate::Term;
 use std::collections::BTreeSet;
@@ -18,6 +19,11 @@ use std::fmt;
 /// * `term_freq`  - number of occurrences of the term in the field
 /// * `field norm` - number of tokens in the field.
 ///
+/// If the field was indexed without term frequencies
+/// (`IndexRecordOption::Basic`), `term_freq` is taken to be 1 for every
+/// document, so that such fields still get a non-zero score that only
+/// depends on `idf` and `field norm`.
+///
 /// ```rust
 /// use tantivy::collector::{Count, TopDocs};
 /// use tantivy::query::TermQuery;
@@ -60,6 +66,7 @@ use std::fmt;
 pub struct TermQuery {
     term: Term,
     index_record_option: IndexRecordOption,
+    bm25_params: Option<(Score, Score)>,
 }
 
 impl fmt::Debug for TermQuery {
@@ -74,9 +81,35 @@ impl TermQuery {
         TermQuery {
             term,
             index_record_option: segment_postings_options,
+            bm25_params: None,
         }
     }
 
+    /// Creates a term query matching the documents whose `bool` field
+    /// has the value `val`.
+    pub fn from_bool(field: Field, val: bool) -> TermQuery {
+        TermQuery::new(Term::from_field_bool(field, val), IndexRecordOption::Basic)
+    }
+
+    /// Scores the matching documents using the given BM25 parameters
+    /// instead of the default `k1 = 1.2` and `b = 0.75`.
+    ///
+    /// `k1` controls how fast the term frequency saturates, and `b` how much
+    /// documents longer than average are penalized (`b = 0` disables
+    /// length normalization).
+    ///
+    /// Only `TermQuery` accepts custom BM25 parameters. `PhraseQuery`,
+    /// the term queries built by `BooleanQuery`'s multiterms helpers and the
+    /// queries built by the `QueryParser` are always scored with the defaults.
+    ///
+    /// # Panics
+    /// When the query is scored, if `k1` is negative or if `b` is not
+    /// within `[0, 1]`.
+    pub fn with_bm25_params(mut self, k1: Score, b: Score) -> TermQuery {
+        self.bm25_params = Some((k1, b));
+        self
+    }
+
     /// The `Term` this query is built out of.
     pub fn term(&self) -> &Term {
         &self.term
@@ -102,7 +135,11 @@ impl TermQuery {
         }
         let bm25_weight;
         if scoring_enabled {
-            bm25_weight = BM25Weight::for_terms(searcher, &[term])?;
+            let default_bm25_weight = BM25Weight::for_terms(searcher, &[term])?;
+            bm25_weight = match self.bm25_params {
+                Some((k1, b)) => default_bm25_weight.with_params(k1, b),
+                None => default_bm25_weight,
+            };
         } else {
             bm25_weight =
                 BM25Weight::new(Explanation::new("<no score>".to_string(), 1.0f32), 1.0f32);
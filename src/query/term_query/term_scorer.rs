@@ -1,4 +1,4 @@
-use crate::docset::DocSet;
+use crate::docset::{DocSet, TERMINATED};
 use crate::query::{Explanation, Scorer};
 use crate::DocId;
 use crate::Score;
@@ -71,7 +71,13 @@ impl TermScorer {
     /// specific is achieved on a different document.
     ///
     /// (The result is on the other hand guaranteed to be correct if there is only one segment).
+    ///
+    /// If the BM25 parameters are not the defaults, the stored pair may not maximize the
+    /// score anymore, and the (looser) maximum score of the term is returned instead.
     pub fn block_max_score(&mut self) -> Score {
+        if !self.similarity_weight.has_default_params() {
+            return self.max_score();
+        }
         self.postings
             .block_cursor
             .block_max_score(&self.fieldnorm_reader, &self.similarity_weight)
@@ -98,6 +104,29 @@ impl TermScorer {
     pub fn last_doc_in_block(&self) -> DocId {
         self.postings.block_cursor.skip_reader.last_doc_in_block()
     }
+
+    /// Advances to the first document, greater or equal to the current one,
+    /// belonging to a block whose block max score exceeds `threshold`.
+    ///
+    /// Blocks that cannot beat the threshold are skipped using the skip information only,
+    /// without being decoded.
+    ///
+    /// The returned document is only a candidate: its actual score may still be
+    /// lower than the threshold.
+    pub fn seek_with_min_score(&mut self, threshold: Score) -> DocId {
+        let mut target = self.doc();
+        loop {
+            self.shallow_seek(target);
+            if self.block_max_score() > threshold {
+                return self.seek(target);
+            }
+            let last_doc_in_block = self.last_doc_in_block();
+            if last_doc_in_block == TERMINATED {
+                return self.seek(TERMINATED);
+            }
+            target = last_doc_in_block + 1;
+        }
+    }
 }
 
 impl DocSet for TermScorer {
@@ -183,6 +212,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_term_scorer_seek_with_min_score() {
+        let bm25_weight = BM25Weight::for_one_term(300, 1024, 10.0);
+        let mut doc_and_tfs = vec![];
+        for i in 0u32..300u32 {
+            let doc = i * 3;
+            // Only the second block contains a document with a high term frequency.
+            let tf = if i == 200 { 10u32 } else { 1u32 };
+            doc_and_tfs.push((doc, tf));
+        }
+        let fieldnorms: Vec<u32> = std::iter::repeat(10u32).take(1_000).collect();
+        let mut term_scorer = TermScorer::create_for_test(&doc_and_tfs, &fieldnorms, bm25_weight);
+        let low_score = term_scorer.score();
+        let first_block_max_score = term_scorer.block_max_score();
+        assert_nearly_equals!(first_block_max_score, low_score);
+        let last_doc_first_block = term_scorer.last_doc_in_block();
+        assert_eq!(last_doc_first_block, 127 * 3);
+
+        // The first block cannot beat the threshold and is skipped entirely.
+        let doc = term_scorer.seek_with_min_score(low_score);
+        assert_eq!(doc, 128 * 3);
+        assert!(term_scorer.block_max_score() > low_score);
+
+        // No block can beat the maximum score.
+        let max_score = term_scorer.max_score();
+        assert_eq!(term_scorer.seek_with_min_score(max_score), TERMINATED);
+    }
+
     proptest! {
         #[test]
         fn test_term_scorer_block_max_score(term_freqs_fieldnorms in proptest::collection::vec((1u32..10u32, 0u32..100u32), 80..300)) {
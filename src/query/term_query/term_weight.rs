@@ -7,7 +7,7 @@ use crate::query::bm25::BM25Weight;
 use crate::query::explanation::does_not_match;
 use crate::query::weight::for_each_scorer;
 use crate::query::Weight;
-use crate::query::{Explanation, Scorer};
+use crate::query::{Explanation, QueryCost, Scorer};
 use crate::schema::IndexRecordOption;
 use crate::Term;
 use crate::{DocId, Score};
@@ -50,6 +50,13 @@ impl Weight for TermWeight {
         }
     }
 
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        let doc_freq = reader
+            .inverted_index(self.term.field())?
+            .doc_freq(&self.term)?;
+        Ok(QueryCost::new(u64::from(doc_freq), 1))
+    }
+
     /// Iterates through all of the document matched by the DocSet
     /// `DocSet` and push the scored documents to the collector.
     fn for_each(
@@ -0,0 +1,187 @@
+use crate::docset::{DocSet, TERMINATED};
+use crate::query::Scorer;
+use crate::DocId;
+use crate::Score;
+
+/// `UnionScorer` iterates through the union of several scorers and, contrary to
+/// [`Union`](./struct.Union.html), gives access to the individual scorers matching
+/// the current document.
+///
+/// Its score is the sum of the scores of the scorers positioned on the current document.
+///
+/// Scorers lagging behind are advanced using `.seek(..)`, making it possible
+/// to skip over entire blocks of their posting lists.
+pub struct UnionScorer<TScorer> {
+    scorers: Vec<TScorer>,
+    doc: DocId,
+}
+
+impl<TScorer: Scorer> From<Vec<TScorer>> for UnionScorer<TScorer> {
+    fn from(scorers: Vec<TScorer>) -> UnionScorer<TScorer> {
+        let mut union = UnionScorer {
+            scorers,
+            doc: TERMINATED,
+        };
+        union.doc = union.min_doc();
+        union
+    }
+}
+
+impl<TScorer: Scorer> UnionScorer<TScorer> {
+    fn min_doc(&self) -> DocId {
+        self.scorers
+            .iter()
+            .map(DocSet::doc)
+            .min()
+            .unwrap_or(TERMINATED)
+    }
+
+    /// Returns the ordinals of the scorers positioned on the current document.
+    ///
+    /// Ordinals refer to the order of the scorers given at construction.
+    pub fn matching_ordinals(&self) -> Vec<usize> {
+        let doc = self.doc;
+        if doc == TERMINATED {
+            return Vec::new();
+        }
+        self.scorers
+            .iter()
+            .enumerate()
+            .filter(|(_, scorer)| scorer.doc() == doc)
+            .map(|(ord, _)| ord)
+            .collect()
+    }
+
+    /// Returns the `(ordinal, score)` of the scorers positioned
+    /// on the current document.
+    pub fn matching_scores(&mut self) -> Vec<(usize, Score)> {
+        let doc = self.doc;
+        if doc == TERMINATED {
+            return Vec::new();
+        }
+        self.scorers
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, scorer)| scorer.doc() == doc)
+            .map(|(ord, scorer)| (ord, scorer.score()))
+            .collect()
+    }
+
+    /// Returns the underlying scorers.
+    pub fn scorers(&self) -> &[TScorer] {
+        &self.scorers[..]
+    }
+}
+
+impl<TScorer: Scorer> DocSet for UnionScorer<TScorer> {
+    fn advance(&mut self) -> DocId {
+        if self.doc == TERMINATED {
+            return TERMINATED;
+        }
+        let doc = self.doc;
+        for scorer in &mut self.scorers {
+            if scorer.doc() == doc {
+                scorer.advance();
+            }
+        }
+        self.doc = self.min_doc();
+        self.doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.doc >= target {
+            return self.doc;
+        }
+        for scorer in &mut self.scorers {
+            if scorer.doc() < target {
+                scorer.seek(target);
+            }
+        }
+        self.doc = self.min_doc();
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.scorers
+            .iter()
+            .map(DocSet::size_hint)
+            .max()
+            .unwrap_or(0u32)
+    }
+}
+
+impl<TScorer: Scorer> Scorer for UnionScorer<TScorer> {
+    fn score(&mut self) -> Score {
+        let doc = self.doc;
+        if doc == TERMINATED {
+            return 0.0;
+        }
+        let mut score = 0.0;
+        for scorer in &mut self.scorers {
+            if scorer.doc() == doc {
+                score += scorer.score();
+            }
+        }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionScorer;
+    use crate::docset::{DocSet, TERMINATED};
+    use crate::query::{ConstScorer, Scorer, VecDocSet};
+
+    fn const_scorer(docs: Vec<u32>, score: f32) -> ConstScorer<VecDocSet> {
+        ConstScorer::new(VecDocSet::from(docs), score)
+    }
+
+    #[test]
+    fn test_union_scorer_sums_matching_scores() {
+        let mut union = UnionScorer::from(vec![
+            const_scorer(vec![1, 3, 5], 1.0),
+            const_scorer(vec![3, 4], 2.0),
+            const_scorer(vec![0, 4, 6], 4.0),
+        ]);
+        assert_eq!(union.doc(), 0);
+        assert_eq!(union.matching_ordinals(), vec![2]);
+        assert_eq!(union.score(), 4.0);
+        assert_eq!(union.advance(), 1);
+        assert_eq!(union.score(), 1.0);
+        assert_eq!(union.advance(), 3);
+        assert_eq!(union.matching_scores(), vec![(0, 1.0), (1, 2.0)]);
+        assert_eq!(union.score(), 3.0);
+        assert_eq!(union.advance(), 4);
+        assert_eq!(union.matching_ordinals(), vec![1, 2]);
+        assert_eq!(union.score(), 6.0);
+        assert_eq!(union.advance(), 5);
+        assert_eq!(union.advance(), 6);
+        assert_eq!(union.advance(), TERMINATED);
+        assert!(union.matching_scores().is_empty());
+    }
+
+    #[test]
+    fn test_union_scorer_seek() {
+        let mut union = UnionScorer::from(vec![
+            const_scorer(vec![1, 3, 5, 9], 1.0),
+            const_scorer(vec![3, 4, 9], 2.0),
+            const_scorer(vec![0, 4, 6], 4.0),
+        ]);
+        assert_eq!(union.seek(4), 4);
+        assert_eq!(union.matching_ordinals(), vec![1, 2]);
+        assert_eq!(union.seek(7), 9);
+        assert_eq!(union.score(), 3.0);
+        assert_eq!(union.seek(10), TERMINATED);
+    }
+
+    #[test]
+    fn test_union_scorer_empty() {
+        let mut union: UnionScorer<ConstScorer<VecDocSet>> = UnionScorer::from(vec![]);
+        assert_eq!(union.doc(), TERMINATED);
+        assert_eq!(union.advance(), TERMINATED);
+    }
+}
@@ -1,6 +1,6 @@
 use super::Scorer;
 use crate::core::SegmentReader;
-use crate::query::Explanation;
+use crate::query::{Explanation, QueryCost};
 use crate::{DocId, Score, TERMINATED};
 
 /// Iterates through all of the document matched by the DocSet
@@ -66,6 +66,15 @@ pub trait Weight: Send + Sync + 'static {
         }
     }
 
+    /// Returns an estimate of the cost of running the weight on the given
+    /// `SegmentReader`, without decoding any posting list.
+    ///
+    /// The default implementation conservatively assumes a full scan
+    /// of the segment.
+    fn estimate_cost(&self, reader: &SegmentReader) -> crate::Result<QueryCost> {
+        Ok(QueryCost::new(u64::from(reader.max_doc()), 0))
+    }
+
     /// Iterates through all of the document matched by the DocSet
     /// `DocSet` and push the scored documents to the collector.
     fn for_each(
@@ -4,14 +4,16 @@ use tantivy_fst::Ulen;
 
 pub use self::pool::LeasedItem;
 use self::pool::Pool;
-use crate::core::Segment;
+use crate::core::{Segment, SegmentId};
 use crate::directory::WatchHandle;
 use crate::directory::META_LOCK;
 use crate::directory::{Directory, WatchCallback};
 use crate::Index;
+use crate::Opstamp;
 use crate::Searcher;
 use crate::SegmentReader;
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::{convert::TryInto, io};
 
 /// Defines when a new version of the index should be reloaded.
@@ -68,6 +70,7 @@ impl IndexReaderBuilder {
             index: self.index,
             num_searchers: self.num_searchers,
             searcher_pool: Pool::new(),
+            segment_reader_cache: Default::default(),
         };
         inner_reader.reload()?;
         let inner_reader_arc = Arc::new(inner_reader);
@@ -127,6 +130,9 @@ struct InnerIndexReader {
     num_searchers: usize,
     searcher_pool: Pool<Searcher>,
     index: Index,
+    /// Segment readers of the last reload, along with the delete opstamp
+    /// of their segment, so that unchanged segments are not reopened.
+    segment_reader_cache: Mutex<HashMap<SegmentId, (Option<Opstamp>, SegmentReader)>>,
 }
 
 impl InnerIndexReader {
@@ -134,10 +140,25 @@ impl InnerIndexReader {
         let segment_readers: Vec<SegmentReader> = {
             let _meta_lock = self.index.directory().acquire_lock(&META_LOCK)?;
             let searchable_segments = self.searchable_segments()?;
-            searchable_segments
-                .iter()
-                .map(SegmentReader::open)
-                .collect::<crate::Result<_>>()?
+            let mut segment_reader_cache = self.segment_reader_cache.lock().unwrap();
+            let mut new_segment_reader_cache = HashMap::with_capacity(searchable_segments.len());
+            let mut segment_readers = Vec::with_capacity(searchable_segments.len());
+            for segment in &searchable_segments {
+                let delete_opstamp = segment.meta().delete_opstamp();
+                let segment_reader = match segment_reader_cache.remove(&segment.id()) {
+                    Some((cached_delete_opstamp, segment_reader))
+                        if cached_delete_opstamp == delete_opstamp =>
+                    {
+                        segment_reader
+                    }
+                    _ => SegmentReader::open(segment)?,
+                };
+                new_segment_reader_cache
+                    .insert(segment.id(), (delete_opstamp, segment_reader.clone()));
+                segment_readers.push(segment_reader);
+            }
+            *segment_reader_cache = new_segment_reader_cache;
+            segment_readers
         };
         let schema = self.index.schema();
         let searchers: Vec<Searcher> = std::iter::repeat_with(|| {
@@ -187,6 +208,9 @@ impl IndexReader {
     ///
     /// This automatic reload can take 10s of milliseconds to kick in however, and in unit tests
     /// it can be nice to deterministically force the reload of searchers.
+    ///
+    /// Segment readers are only opened for the new segments, and for the
+    /// segments with newly committed deletes. The others are reused.
     pub fn reload(&self) -> crate::Result<()> {
         self.inner.reload()
     }
@@ -205,3 +229,66 @@ impl IndexReader {
         self.inner.searcher()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collector::Count;
+    use crate::core::InvertedIndexReader;
+    use crate::query::AllQuery;
+    use crate::schema::{Field, Schema, STRING};
+    use crate::{Index, IndexReader, ReloadPolicy, SegmentId, Term};
+    use std::sync::Arc;
+
+    // The inverted index readers are cached by their segment reader,
+    // so they are shared only if the segment reader was reused.
+    fn inverted_index(
+        reader: &IndexReader,
+        segment_id: SegmentId,
+        field: Field,
+    ) -> crate::Result<Arc<InvertedIndexReader>> {
+        reader
+            .searcher()
+            .segment_readers()
+            .iter()
+            .find(|segment_reader| segment_reader.segment_id() == segment_id)
+            .unwrap()
+            .inverted_index(field)
+    }
+
+    #[test]
+    fn test_reload_reuses_unchanged_segment_readers() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let id = schema_builder.add_text_field("id", STRING);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        index_writer.add_document(doc!(id=>"a"));
+        index_writer.add_document(doc!(id=>"b"));
+        index_writer.commit()?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::Manual)
+            .try_into()?;
+        let first_segment_id = reader.searcher().segment_reader(0).segment_id();
+        let first_inverted_index = inverted_index(&reader, first_segment_id, id)?;
+
+        index_writer.add_document(doc!(id=>"c"));
+        index_writer.commit()?;
+        reader.reload()?;
+        assert_eq!(reader.searcher().segment_readers().len(), 2);
+        assert_eq!(reader.searcher().search(&AllQuery, &Count)?, 3);
+        assert!(Arc::ptr_eq(
+            &first_inverted_index,
+            &inverted_index(&reader, first_segment_id, id)?
+        ));
+
+        index_writer.delete_term(Term::from_field_text(id, "b"));
+        index_writer.commit()?;
+        reader.reload()?;
+        assert_eq!(reader.searcher().search(&AllQuery, &Count)?, 2);
+        assert!(!Arc::ptr_eq(
+            &first_inverted_index,
+            &inverted_index(&reader, first_segment_id, id)?
+        ));
+        Ok(())
+    }
+}
@@ -110,6 +110,11 @@ impl Document {
         self.add(FieldValue::new(field, Value::Date(*value)));
     }
 
+    /// Add a bool field
+    pub fn add_bool(&mut self, field: Field, value: bool) {
+        self.add(FieldValue::new(field, Value::Bool(value)));
+    }
+
     /// Add a bytes field
     pub fn add_bytes<T: Into<Vec<u8>>>(&mut self, field: Field, value: T) {
         self.add(FieldValue::new(field, Value::Bytes(value.into())))
@@ -210,6 +215,18 @@ impl BinarySerializable for Document {
     }
 }
 
+impl Document {
+    /// Deserializes a `Document`, replacing invalid UTF-8 sequences
+    /// in text values by `U+FFFD REPLACEMENT CHARACTER`.
+    pub(crate) fn deserialize_lossy_utf8<R: Read>(reader: &mut R) -> io::Result<Document> {
+        let num_field_values = VInt::deserialize(reader)?.val() as Ulen;
+        let field_values = (0..num_field_values)
+            .map(|_| FieldValue::deserialize_lossy_utf8(reader))
+            .collect::<io::Result<Vec<FieldValue>>>()?;
+        Ok(Document::from(field_values))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -72,6 +72,16 @@ impl FieldEntry {
         }
     }
 
+    /// Creates a new bool field entry in the schema, given
+    /// a name, and some options.
+    pub fn new_bool(field_name: String, field_type: IntOptions) -> FieldEntry {
+        assert!(is_valid_field_name(&field_name));
+        FieldEntry {
+            name: field_name,
+            field_type: FieldType::Bool(field_type),
+        }
+    }
+
     /// Creates a field entry for a facet.
     pub fn new_facet(field_name: String) -> FieldEntry {
         assert!(is_valid_field_name(&field_name));
@@ -106,7 +116,8 @@ impl FieldEntry {
             FieldType::U64(ref options)
             | FieldType::I64(ref options)
             | FieldType::F64(ref options)
-            | FieldType::Date(ref options) => options.is_indexed(),
+            | FieldType::Date(ref options)
+            | FieldType::Bool(ref options) => options.is_indexed(),
             FieldType::HierarchicalFacet => true,
             FieldType::Bytes(ref options) => options.is_indexed(),
         }
@@ -118,7 +129,8 @@ impl FieldEntry {
             FieldType::U64(ref options)
             | FieldType::I64(ref options)
             | FieldType::Date(ref options)
-            | FieldType::F64(ref options) => options.is_fast(),
+            | FieldType::F64(ref options)
+            | FieldType::Bool(ref options) => options.is_fast(),
             _ => false,
         }
     }
@@ -129,7 +141,8 @@ impl FieldEntry {
             FieldType::U64(ref options)
             | FieldType::I64(ref options)
             | FieldType::F64(ref options)
-            | FieldType::Date(ref options) => options.is_stored(),
+            | FieldType::Date(ref options)
+            | FieldType::Bool(ref options) => options.is_stored(),
             FieldType::Str(ref options) => options.is_stored(),
             // TODO make stored hierarchical facet optional
             FieldType::HierarchicalFacet => true,
@@ -167,6 +180,10 @@ impl Serialize for FieldEntry {
                 s.serialize_field("type", "date")?;
                 s.serialize_field("options", options)?;
             }
+            FieldType::Bool(ref options) => {
+                s.serialize_field("type", "bool")?;
+                s.serialize_field("options", options)?;
+            }
             FieldType::HierarchicalFacet => {
                 s.serialize_field("type", "hierarchical_facet")?;
             }
@@ -228,7 +245,7 @@ impl<'de> Deserialize<'de> for FieldEntry {
                                 "hierarchical_facet" => {
                                     field_type = Some(FieldType::HierarchicalFacet);
                                 }
-                                "text" | "u64" | "i64" | "f64" | "date" | "bytes" => {
+                                "text" | "u64" | "i64" | "f64" | "date" | "bool" | "bytes" => {
                                     // These types require additional options to create a field_type
                                 }
                                 _ => panic!("unhandled type"),
@@ -247,6 +264,7 @@ impl<'de> Deserialize<'de> for FieldEntry {
                                 "i64" => field_type = Some(FieldType::I64(map.next_value()?)),
                                 "f64" => field_type = Some(FieldType::F64(map.next_value()?)),
                                 "date" => field_type = Some(FieldType::Date(map.next_value()?)),
+                                "bool" => field_type = Some(FieldType::Bool(map.next_value()?)),
                                 "bytes" => field_type = Some(FieldType::Bytes(map.next_value()?)),
                                 _ => {
                                     let msg = format!("Unrecognised type {}", ty);
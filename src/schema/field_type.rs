@@ -43,6 +43,8 @@ pub enum Type {
     HierarchicalFacet,
     /// `Vec<u8>`
     Bytes,
+    /// `bool`
+    Bool,
 }
 
 /// A `FieldType` describes the type (text, u64) of a field as well as
@@ -63,6 +65,8 @@ pub enum FieldType {
     HierarchicalFacet,
     /// Bytes (one per document)
     Bytes(BytesOptions),
+    /// Boolean field type configuration
+    Bool(IntOptions),
 }
 
 impl FieldType {
@@ -76,6 +80,7 @@ impl FieldType {
             FieldType::Date(_) => Type::Date,
             FieldType::HierarchicalFacet => Type::HierarchicalFacet,
             FieldType::Bytes(_) => Type::Bytes,
+            FieldType::Bool(_) => Type::Bool,
         }
     }
 
@@ -89,6 +94,7 @@ impl FieldType {
             FieldType::Date(ref date_options) => date_options.is_indexed(),
             FieldType::HierarchicalFacet => true,
             FieldType::Bytes(ref bytes_options) => bytes_options.is_indexed(),
+            FieldType::Bool(ref bool_options) => bool_options.is_indexed(),
         }
     }
 
@@ -104,7 +110,8 @@ impl FieldType {
             FieldType::U64(ref int_options)
             | FieldType::I64(ref int_options)
             | FieldType::F64(ref int_options)
-            | FieldType::Date(ref int_options) => {
+            | FieldType::Date(ref int_options)
+            | FieldType::Bool(ref int_options) => {
                 if int_options.is_indexed() {
                     Some(IndexRecordOption::Basic)
                 } else {
@@ -144,6 +151,10 @@ impl FieldType {
                 FieldType::U64(_) | FieldType::I64(_) | FieldType::F64(_) => Err(
                     ValueParsingError::TypeError(format!("Expected an integer, got {:?}", json)),
                 ),
+                FieldType::Bool(_) => Err(ValueParsingError::TypeError(format!(
+                    "Expected a bool, got {:?}",
+                    json
+                ))),
                 FieldType::HierarchicalFacet => Ok(Value::Facet(Facet::from(field_text))),
                 FieldType::Bytes(_) => base64::decode(field_text).map(Value::Bytes).map_err(|_| {
                     ValueParsingError::InvalidBase64(format!(
@@ -181,6 +192,17 @@ impl FieldType {
                     let msg = format!("Expected a string, got {:?}", json);
                     Err(ValueParsingError::TypeError(msg))
                 }
+                FieldType::Bool(_) => {
+                    let msg = format!("Expected a bool, got {:?}", json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
+            },
+            JsonValue::Bool(field_val_bool) => match *self {
+                FieldType::Bool(_) => Ok(Value::Bool(field_val_bool)),
+                _ => {
+                    let msg = format!("Expected {:?}, got a bool {:?}", self, json);
+                    Err(ValueParsingError::TypeError(msg))
+                }
             },
             JsonValue::Object(_) => match *self {
                 FieldType::Str(_) => {
@@ -1,5 +1,6 @@
 use crate::common::BinarySerializable;
 use crate::schema::Field;
+use crate::schema::value::deserialize_value;
 use crate::schema::Value;
 use std::io::{self, Read, Write};
 
@@ -25,6 +26,14 @@ impl FieldValue {
     pub fn value(&self) -> &Value {
         &self.value
     }
+
+    /// Deserializes a `FieldValue`, replacing invalid UTF-8 sequences
+    /// in text values by `U+FFFD REPLACEMENT CHARACTER`.
+    pub(crate) fn deserialize_lossy_utf8<R: Read>(reader: &mut R) -> io::Result<FieldValue> {
+        let field = Field::deserialize(reader)?;
+        let value = deserialize_value(reader, true)?;
+        Ok(FieldValue::new(field, value))
+    }
 }
 
 impl BinarySerializable for FieldValue {
@@ -126,6 +126,31 @@ impl SchemaBuilder {
         self.add_field(field_entry)
     }
 
+    /// Adds a new bool field.
+    /// Returns the associated field handle
+    ///
+    /// Bool fields are indexed as a single term per value
+    /// and can be used as filters with a `TermQuery`.
+    /// If the field is `FAST`, values are stored as `0`/`1`
+    /// in a u64 fast field.
+    ///
+    /// # Caution
+    ///
+    /// Appending two fields with the same name
+    /// will result in the shadowing of the first
+    /// by the second one.
+    /// The first field will get a field id
+    /// but only the second one will be indexed
+    pub fn add_bool_field<T: Into<IntOptions>>(
+        &mut self,
+        field_name_str: &str,
+        field_options: T,
+    ) -> Field {
+        let field_name = String::from(field_name_str);
+        let field_entry = FieldEntry::new_bool(field_name, field_options.into());
+        self.add_field(field_entry)
+    }
+
     /// Adds a new text field.
     /// Returns the associated field handle
     ///
@@ -98,6 +98,16 @@ impl Term {
         term
     }
 
+    /// Builds a term given a field, and a bool value.
+    ///
+    /// The value is encoded as a single byte, `0` for `false`
+    /// and `1` for `true`.
+    pub fn from_field_bool(field: Field, val: bool) -> Term {
+        let mut term = Term::for_field(field);
+        term.set_bool(val);
+        term
+    }
+
     /// Builds a term bytes.
     pub fn from_field_bytes(field: Field, bytes: &[u8]) -> Term {
         let mut term = Term::for_field(field);
@@ -139,6 +149,11 @@ impl Term {
         self.set_u64(common::f64_to_u64(val));
     }
 
+    /// Sets a `bool` value in the term.
+    pub fn set_bool(&mut self, val: bool) {
+        self.set_bytes(&[val as u8]);
+    }
+
     /// Sets the value of a `Bytes` field.
     pub fn set_bytes(&mut self, bytes: &[u8]) {
         self.0.resize(4, 0u8);
@@ -1,6 +1,7 @@
 use crate::schema::flags::SchemaFlagList;
 use crate::schema::flags::StoredFlag;
 use crate::schema::IndexRecordOption;
+use crate::tokenizer::AnalyzerConfig;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::ops::BitOr;
@@ -51,10 +52,13 @@ impl Default for TextOptions {
 /// - the amount of information that should be stored about the presence of a term in a document.
 /// Essentially, should we store the term frequency and/or the positions (See [`IndexRecordOption`](./enum.IndexRecordOption.html)).
 /// - the name of the `Tokenizer` that should be used to process the field.
+/// - optionally, the configuration of this tokenizer, persisted with the schema.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct TextFieldIndexing {
     record: IndexRecordOption,
     tokenizer: Cow<'static, str>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    analyzer: Option<AnalyzerConfig>,
 }
 
 impl Default for TextFieldIndexing {
@@ -62,6 +66,7 @@ impl Default for TextFieldIndexing {
         TextFieldIndexing {
             tokenizer: Cow::Borrowed("default"),
             record: IndexRecordOption::Basic,
+            analyzer: None,
         }
     }
 }
@@ -78,6 +83,32 @@ impl TextFieldIndexing {
         &self.tokenizer
     }
 
+    /// Sets the tokenizer to be used for a given field, together with its configuration.
+    ///
+    /// The configuration is persisted with the schema. When the index is
+    /// created or opened, it is registered in the index's `TokenizerManager`
+    /// under `tokenizer_name`, and the analyzer is built from the tokenizers
+    /// of that `TokenizerManager` whenever it is looked up.
+    ///
+    /// `tokenizer_name` cannot be the name of a built-in tokenizer, and fields
+    /// sharing a `tokenizer_name` must share the same configuration: otherwise
+    /// creating or opening the index fails with a `SchemaError`.
+    pub fn set_analyzer(
+        mut self,
+        tokenizer_name: &str,
+        analyzer_config: AnalyzerConfig,
+    ) -> TextFieldIndexing {
+        self.tokenizer = Cow::Owned(tokenizer_name.to_string());
+        self.analyzer = Some(analyzer_config);
+        self
+    }
+
+    /// Returns the configuration of the tokenizer, if it was
+    /// set with `set_analyzer`.
+    pub fn analyzer(&self) -> Option<&AnalyzerConfig> {
+        self.analyzer.as_ref()
+    }
+
     /// Sets which information should be indexed with the tokens.
     ///
     /// See [IndexRecordOption](./enum.IndexRecordOption.html) for more detail.
@@ -99,6 +130,7 @@ pub const STRING: TextOptions = TextOptions {
     indexing: Some(TextFieldIndexing {
         tokenizer: Cow::Borrowed("raw"),
         record: IndexRecordOption::Basic,
+        analyzer: None,
     }),
     stored: false,
 };
@@ -108,6 +140,7 @@ pub const TEXT: TextOptions = TextOptions {
     indexing: Some(TextFieldIndexing {
         tokenizer: Cow::Borrowed("default"),
         record: IndexRecordOption::WithFreqsAndPositions,
+        analyzer: None,
     }),
     stored: false,
 };
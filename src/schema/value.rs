@@ -25,6 +25,8 @@ pub enum Value {
     Facet(Facet),
     /// Arbitrarily sized byte array
     Bytes(Vec<u8>),
+    /// Boolean `bool`
+    Bool(bool),
 }
 
 impl Eq for Value {}
@@ -43,6 +45,7 @@ impl Ord for Value {
             (Value::Date(l), Value::Date(r)) => l.cmp(r),
             (Value::Facet(l), Value::Facet(r)) => l.cmp(r),
             (Value::Bytes(l), Value::Bytes(r)) => l.cmp(r),
+            (Value::Bool(l), Value::Bool(r)) => l.cmp(r),
             (Value::F64(l), Value::F64(r)) => {
                 match (l.is_nan(), r.is_nan()) {
                     (false, false) => l.partial_cmp(r).unwrap(), // only fail on NaN
@@ -65,6 +68,8 @@ impl Ord for Value {
             (_, Value::Date(_)) => Ordering::Greater,
             (Value::Facet(_), _) => Ordering::Less,
             (_, Value::Facet(_)) => Ordering::Greater,
+            (Value::Bytes(_), _) => Ordering::Less,
+            (_, Value::Bytes(_)) => Ordering::Greater,
         }
     }
 }
@@ -83,6 +88,7 @@ impl Serialize for Value {
             Value::Date(ref date) => serializer.serialize_str(&date.to_rfc3339()),
             Value::Facet(ref facet) => facet.serialize(serializer),
             Value::Bytes(ref bytes) => serializer.serialize_bytes(bytes),
+            Value::Bool(b) => serializer.serialize_bool(b),
         }
     }
 }
@@ -101,6 +107,10 @@ impl<'de> Deserialize<'de> for Value {
                 formatter.write_str("a string or u32")
             }
 
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+                Ok(Value::Bool(v))
+            }
+
             fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
                 Ok(Value::I64(v))
             }
@@ -192,6 +202,17 @@ impl Value {
         }
     }
 
+    /// Returns the bool-value, provided the value is of the `Bool` type.
+    ///
+    /// Returns None if the value is not of type `Bool`.
+    pub fn bool_value(&self) -> Option<bool> {
+        if let Value::Bool(val) = self {
+            Some(*val)
+        } else {
+            None
+        }
+    }
+
     /// Returns the Bytes-value, provided the value is of the `Bytes` type.
     ///
     /// Returns None if the value is not of type `Bytes`.
@@ -228,6 +249,12 @@ impl From<f64> for Value {
     }
 }
 
+impl From<bool> for Value {
+    fn from(v: bool) -> Value {
+        Value::Bool(v)
+    }
+}
+
 impl From<crate::DateTime> for Value {
     fn from(date_time: crate::DateTime) -> Value {
         Value::Date(date_time)
@@ -264,6 +291,8 @@ impl From<PreTokenizedString> for Value {
     }
 }
 
+pub(crate) use self::binary_serialize::deserialize_value;
+
 mod binary_serialize {
     use super::Value;
     use crate::common::{f64_to_u64, u64_to_f64, BinarySerializable};
@@ -280,6 +309,7 @@ mod binary_serialize {
     const DATE_CODE: u8 = 5;
     const F64_CODE: u8 = 6;
     const EXT_CODE: u8 = 7;
+    const BOOL_CODE: u8 = 8;
 
     // extended types
 
@@ -328,62 +358,86 @@ mod binary_serialize {
                     BYTES_CODE.serialize(writer)?;
                     bytes.serialize(writer)
                 }
+                Value::Bool(val) => {
+                    BOOL_CODE.serialize(writer)?;
+                    (val as u8).serialize(writer)
+                }
             }
         }
         fn deserialize<R: Read>(reader: &mut R) -> io::Result<Self> {
-            let type_code = u8::deserialize(reader)?;
-            match type_code {
-                TEXT_CODE => {
-                    let text = String::deserialize(reader)?;
-                    Ok(Value::Str(text))
-                }
-                U64_CODE => {
-                    let value = u64::deserialize(reader)?;
-                    Ok(Value::U64(value))
-                }
-                I64_CODE => {
-                    let value = i64::deserialize(reader)?;
-                    Ok(Value::I64(value))
-                }
-                F64_CODE => {
-                    let value = u64_to_f64(u64::deserialize(reader)?);
-                    Ok(Value::F64(value))
-                }
-                DATE_CODE => {
-                    let timestamp = i64::deserialize(reader)?;
-                    Ok(Value::Date(Utc.timestamp(timestamp, 0)))
-                }
-                HIERARCHICAL_FACET_CODE => Ok(Value::Facet(Facet::deserialize(reader)?)),
-                BYTES_CODE => Ok(Value::Bytes(Vec::<u8>::deserialize(reader)?)),
-                EXT_CODE => {
-                    let ext_type_code = u8::deserialize(reader)?;
-                    match ext_type_code {
-                        TOK_STR_CODE => {
-                            let str_val = String::deserialize(reader)?;
-                            if let Ok(value) = serde_json::from_str::<PreTokenizedString>(&str_val)
-                            {
-                                Ok(Value::PreTokStr(value))
-                            } else {
-                                Err(io::Error::new(
-                                    io::ErrorKind::Other,
-                                    "Failed to parse string data as Value::PreTokStr(_).",
-                                ))
-                            }
+            deserialize_value(reader, false)
+        }
+    }
+
+    fn deserialize_text<R: Read>(reader: &mut R, lossy_utf8: bool) -> io::Result<String> {
+        let bytes = Vec::<u8>::deserialize(reader)?;
+        match String::from_utf8(bytes) {
+            Ok(text) => Ok(text),
+            Err(utf8_err) if lossy_utf8 => {
+                Ok(String::from_utf8_lossy(utf8_err.as_bytes()).into_owned())
+            }
+            Err(utf8_err) => Err(io::Error::new(io::ErrorKind::InvalidData, utf8_err)),
+        }
+    }
+
+    /// Deserializes a `Value`.
+    ///
+    /// If `lossy_utf8` is set, invalid UTF-8 sequences in text values are
+    /// replaced by `U+FFFD REPLACEMENT CHARACTER` instead of returning an error.
+    pub(crate) fn deserialize_value<R: Read>(reader: &mut R, lossy_utf8: bool) -> io::Result<Value> {
+        let type_code = u8::deserialize(reader)?;
+        match type_code {
+            TEXT_CODE => {
+                let text = deserialize_text(reader, lossy_utf8)?;
+                Ok(Value::Str(text))
+            }
+            U64_CODE => {
+                let value = u64::deserialize(reader)?;
+                Ok(Value::U64(value))
+            }
+            I64_CODE => {
+                let value = i64::deserialize(reader)?;
+                Ok(Value::I64(value))
+            }
+            F64_CODE => {
+                let value = u64_to_f64(u64::deserialize(reader)?);
+                Ok(Value::F64(value))
+            }
+            DATE_CODE => {
+                let timestamp = i64::deserialize(reader)?;
+                Ok(Value::Date(Utc.timestamp(timestamp, 0)))
+            }
+            HIERARCHICAL_FACET_CODE => Ok(Value::Facet(Facet::deserialize(reader)?)),
+            BYTES_CODE => Ok(Value::Bytes(Vec::<u8>::deserialize(reader)?)),
+            BOOL_CODE => Ok(Value::Bool(u8::deserialize(reader)? != 0)),
+            EXT_CODE => {
+                let ext_type_code = u8::deserialize(reader)?;
+                match ext_type_code {
+                    TOK_STR_CODE => {
+                        let str_val = String::deserialize(reader)?;
+                        if let Ok(value) = serde_json::from_str::<PreTokenizedString>(&str_val)
+                        {
+                            Ok(Value::PreTokStr(value))
+                        } else {
+                            Err(io::Error::new(
+                                io::ErrorKind::Other,
+                                "Failed to parse string data as Value::PreTokStr(_).",
+                            ))
                         }
-                        _ => Err(io::Error::new(
-                            io::ErrorKind::InvalidData,
-                            format!(
-                                "No extened field type is associated with code {:?}",
-                                ext_type_code
-                            ),
-                        )),
                     }
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "No extened field type is associated with code {:?}",
+                            ext_type_code
+                        ),
+                    )),
                 }
-                _ => Err(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    format!("No field type is associated with code {:?}", type_code),
-                )),
             }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("No field type is associated with code {:?}", type_code),
+            )),
         }
     }
 }
@@ -319,7 +319,7 @@ mod tests {
     use super::{search_fragments, select_best_fragment_combination};
     use crate::query::QueryParser;
     use crate::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions, TEXT};
-    use crate::tokenizer::SimpleTokenizer;
+    use crate::tokenizer::{SimpleTokenizer, TokenizerManager};
     use crate::Index;
     use crate::SnippetGenerator;
     use maplit::btreemap;
@@ -421,6 +421,26 @@ Survey in 2016, 2017, and 2018."#;
         assert_eq!(snippet.to_html(), "<b>c</b> d");
     }
 
+    #[test]
+    fn test_snippet_stemmed_token_highlights_surface_form() {
+        let text = "she was running fast";
+        let en_stem = TokenizerManager::default().get("en_stem").unwrap();
+
+        let mut terms = BTreeMap::new();
+        terms.insert(String::from("run"), 1.0);
+
+        let fragments = search_fragments(&en_stem, &text, &terms, 100);
+        assert_eq!(fragments.len(), 1);
+        {
+            let first = &fragments[0];
+            assert_eq!(first.highlighted.len(), 1);
+            assert_eq!(first.highlighted[0].bounds(), (8, 15));
+        }
+
+        let snippet = select_best_fragment_combination(&fragments[..], &text);
+        assert_eq!(snippet.to_html(), "she was <b>running</b> fast");
+    }
+
     #[test]
     fn test_snippet_with_term_at_the_end_of_fragment() {
         let text = "a b c d e f f g";
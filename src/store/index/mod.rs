@@ -66,12 +66,44 @@ mod tests {
         let mut output: Vec<u8> = Vec::new();
         let skip_index_builder: SkipIndexBuilder = SkipIndexBuilder::new();
         skip_index_builder.write(&mut output)?;
-        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output));
+        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output))?;
+        assert_eq!(skip_index.len(), 0);
+        assert!(skip_index.is_empty());
         let mut skip_cursor = skip_index.checkpoints();
         assert!(skip_cursor.next().is_none());
         Ok(())
     }
 
+    #[test]
+    fn test_skip_index_corrupted() -> io::Result<()> {
+        let mut output: Vec<u8> = Vec::new();
+        let mut skip_index_builder = SkipIndexBuilder::new();
+        for i in 0..20 {
+            skip_index_builder.insert(Checkpoint {
+                start_doc: i,
+                end_doc: i + 1,
+                start_offset: offset_test(i),
+                end_offset: offset_test(i + 1),
+            });
+        }
+        skip_index_builder.write(&mut output)?;
+        // The header claims more layer bytes than there are.
+        let truncated = output[..output.len() - 1].to_vec();
+        let err = SkipIndex::open(OwnedBytes::new(truncated)).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // The header itself is cut short.
+        let err = SkipIndex::open(OwnedBytes::new(vec![2u8])).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        // An empty layer list is legitimate.
+        let mut empty: Vec<u8> = Vec::new();
+        SkipIndexBuilder::new().write(&mut empty)?;
+        assert!(SkipIndex::open(OwnedBytes::new(empty))?
+            .checkpoints()
+            .next()
+            .is_none());
+        Ok(())
+    }
+
     #[test]
     fn test_skip_index_single_el() -> io::Result<()> {
         let mut output: Vec<u8> = Vec::new();
@@ -84,7 +116,9 @@ mod tests {
         };
         skip_index_builder.insert(checkpoint);
         skip_index_builder.write(&mut output)?;
-        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output));
+        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output))?;
+        assert_eq!(skip_index.len(), 1);
+        assert!(!skip_index.is_empty());
         let mut skip_cursor = skip_index.checkpoints();
         assert_eq!(skip_cursor.next(), Some(checkpoint));
         assert_eq!(skip_cursor.next(), None);
@@ -133,11 +167,17 @@ mod tests {
         }
         skip_index_builder.write(&mut output)?;
 
-        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output));
+        let skip_index: SkipIndex = SkipIndex::open(OwnedBytes::new(output))?;
         assert_eq!(
             &skip_index.checkpoints().collect::<Vec<_>>()[..],
             &checkpoints[..]
         );
+        assert_eq!(skip_index.seek(0), Some(checkpoints[0]));
+        assert_eq!(skip_index.seek(2), Some(checkpoints[0]));
+        assert_eq!(skip_index.seek(3), Some(checkpoints[1]));
+        assert_eq!(skip_index.seek(5), Some(checkpoints[2]));
+        assert_eq!(skip_index.seek(9), Some(checkpoints[4]));
+        assert_eq!(skip_index.seek(10), None);
         Ok(())
     }
 
@@ -145,6 +185,33 @@ mod tests {
         (doc as u64) * (doc as u64)
     }
 
+    #[test]
+    fn test_skip_index_debug_dump() -> io::Result<()> {
+        let mut output: Vec<u8> = Vec::new();
+        let mut skip_index_builder = SkipIndexBuilder::new();
+        for i in 0..9 {
+            skip_index_builder.insert(Checkpoint {
+                start_doc: i,
+                end_doc: i + 1,
+                start_offset: offset_test(i),
+                end_offset: offset_test(i + 1),
+            });
+        }
+        skip_index_builder.write(&mut output)?;
+        let skip_index = SkipIndex::open(OwnedBytes::new(output))?;
+        assert_eq!(
+            format!("{:?}", skip_index),
+            "SkipIndex(num_layers=2)\n\
+             layer 0: [(doc=[0..8), bytes=[0..19)), (doc=[8..9), bytes=[19..24))]\n\
+             layer 1 (data): [(doc=[0..1), bytes=[0..1)), (doc=[1..2), bytes=[1..4)), \
+             (doc=[2..3), bytes=[4..9)), (doc=[3..4), bytes=[9..16)), \
+             (doc=[4..5), bytes=[16..25)), (doc=[5..6), bytes=[25..36)), \
+             (doc=[6..7), bytes=[36..49)), (doc=[7..8), bytes=[49..64)), \
+             (doc=[8..9), bytes=[64..81))]\n"
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_merge_store_with_stacking_reproducing_issue969() -> crate::Result<()> {
         let mut schema_builder = SchemaBuilder::default();
@@ -196,13 +263,141 @@ mod tests {
         }
         skip_index_builder.write(&mut output)?;
         assert_eq!(output.len(), 4035);
-        let resulting_checkpoints: Vec<Checkpoint> = SkipIndex::open(OwnedBytes::new(output))
+        let resulting_checkpoints: Vec<Checkpoint> = SkipIndex::open(OwnedBytes::new(output))?
             .checkpoints()
             .collect();
         assert_eq!(&resulting_checkpoints, &checkpoints);
         Ok(())
     }
 
+    #[test]
+    fn test_skip_index_asymmetric_periods() -> io::Result<()> {
+        let checkpoints: Vec<Checkpoint> = (0..10_000)
+            .map(|i| Checkpoint {
+                start_doc: i * 3,
+                end_doc: i * 3 + 3,
+                start_offset: offset_test(i),
+                end_offset: offset_test(i + 1),
+            })
+            .collect();
+        let mut skip_index_builder = SkipIndexBuilder::with_periods(64, 4);
+        for checkpoint in &checkpoints {
+            skip_index_builder.insert(*checkpoint);
+        }
+        let mut output: Vec<u8> = Vec::new();
+        skip_index_builder.write(&mut output)?;
+        let skip_index = SkipIndex::open(OwnedBytes::new(output))?;
+        assert_eq!(skip_index.len(), 10_000);
+        assert_eq!(skip_index.checkpoints().collect::<Vec<_>>(), checkpoints);
+        for target in (0..30_000).step_by(7) {
+            let checkpoint = skip_index.seek(target).unwrap();
+            assert_eq!(checkpoint, checkpoints[(target / 3) as usize]);
+        }
+        assert!(skip_index.seek(30_000).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_index_serialized_len() -> io::Result<()> {
+        for &(num_checkpoints, data_period, skip_period) in &[
+            (0u32, 8, 8),
+            (1, 8, 8),
+            (7, 8, 8),
+            (8, 8, 8),
+            (100, 8, 8),
+            (10_000, 8, 8),
+            (10_000, 64, 4),
+            (257, 1, 2),
+        ] {
+            let mut skip_index_builder = SkipIndexBuilder::with_periods(data_period, skip_period);
+            for i in 0..num_checkpoints {
+                skip_index_builder.insert(Checkpoint {
+                    start_doc: i * 3,
+                    end_doc: i * 3 + 3,
+                    start_offset: offset_test(i),
+                    end_offset: offset_test(i + 1),
+                });
+            }
+            let serialized_len = skip_index_builder.serialized_len();
+            let mut output: Vec<u8> = Vec::new();
+            skip_index_builder.write(&mut output)?;
+            assert_eq!(serialized_len, output.len());
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "The data period must be at least 1.")]
+    fn test_skip_index_zero_data_period() {
+        SkipIndexBuilder::with_periods(0, 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "The skip period must be at least 2.")]
+    fn test_skip_index_skip_period_too_small() {
+        SkipIndexBuilder::with_periods(8, 1);
+    }
+
+    #[test]
+    fn test_skip_index_layers_are_delta_vint_encoded() -> io::Result<()> {
+        let mut skip_index_builder = SkipIndexBuilder::new();
+        for i in 0..1_000 {
+            skip_index_builder.insert(Checkpoint {
+                start_doc: i,
+                end_doc: i + 1,
+                start_offset: i as u64,
+                end_offset: i as u64 + 1,
+            });
+        }
+        let mut output: Vec<u8> = Vec::new();
+        skip_index_builder.write(&mut output)?;
+        // Fixed width encoding would take 4 bytes per doc id alone.
+        assert!(output.len() < 4 * 1_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_index_large_doc_ids() -> io::Result<()> {
+        let base_doc = DocId::MAX - 100;
+        let checkpoints: Vec<Checkpoint> = (0..50)
+            .map(|i| Checkpoint {
+                start_doc: base_doc + 2 * i,
+                end_doc: base_doc + 2 * i + 2,
+                start_offset: u64::from(u32::MAX) + offset_test(i),
+                end_offset: u64::from(u32::MAX) + offset_test(i + 1),
+            })
+            .collect();
+        let mut skip_index_builder = SkipIndexBuilder::new();
+        for checkpoint in &checkpoints {
+            skip_index_builder.insert(*checkpoint);
+        }
+        let mut output: Vec<u8> = Vec::new();
+        skip_index_builder.write(&mut output)?;
+        let skip_index = SkipIndex::open(OwnedBytes::new(output))?;
+        assert_eq!(skip_index.checkpoints().collect::<Vec<_>>(), checkpoints);
+        assert_eq!(skip_index.seek(base_doc + 51), Some(checkpoints[25]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_index_builder_footprint_is_bounded() -> io::Result<()> {
+        const BLOCK_SIZE: u64 = 16_384;
+        let num_checkpoints = 100_000u32;
+        let mut skip_index_builder = SkipIndexBuilder::new();
+        for i in 0..num_checkpoints {
+            skip_index_builder.insert(Checkpoint {
+                start_doc: i * 10,
+                end_doc: i * 10 + 10,
+                start_offset: i as u64 * BLOCK_SIZE,
+                end_offset: (i as u64 + 1) * BLOCK_SIZE,
+            });
+        }
+        let mut output: Vec<u8> = Vec::new();
+        skip_index_builder.write(&mut output)?;
+        assert!(output.len() < 8 * num_checkpoints as usize);
+        Ok(())
+    }
+
     fn integrate_delta(vals: Vec<u64>) -> Vec<u64> {
         let mut output = Vec::with_capacity(vals.len() + 1);
         output.push(0u64);
@@ -269,7 +464,7 @@ mod tests {
              }
              let mut buffer = Vec::new();
              skip_index_builder.write(&mut buffer).unwrap();
-             let skip_index = SkipIndex::open(OwnedBytes::new(buffer));
+             let skip_index = SkipIndex::open(OwnedBytes::new(buffer)).unwrap();
              let iter_checkpoints: Vec<Checkpoint> = skip_index.checkpoints().collect();
              assert_eq!(&checkpoints[..], &iter_checkpoints[..]);
              test_skip_index_aux(skip_index, &checkpoints[..]);
@@ -5,6 +5,8 @@ use crate::directory::OwnedBytes;
 use crate::store::index::block::CheckpointBlock;
 use crate::store::index::Checkpoint;
 use crate::DocId;
+use std::fmt;
+use std::io;
 
 pub struct LayerCursor<'a> {
     remaining: &'a [u8],
@@ -60,23 +62,63 @@ pub struct SkipIndex {
     layers: Vec<Layer>,
 }
 
+/// Dumps every layer of the skip index, from the top-most skip layer
+/// down to the data layer.
+///
+/// The checkpoints of a skip layer point to byte ranges in the layer below it.
+impl fmt::Debug for SkipIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "SkipIndex(num_layers={})", self.layers.len())?;
+        for (layer_id, layer) in self.layers.iter().enumerate() {
+            let checkpoints: Vec<Checkpoint> = layer.cursor().collect();
+            if layer_id + 1 == self.layers.len() {
+                writeln!(f, "layer {} (data): {:?}", layer_id, checkpoints)?;
+            } else {
+                writeln!(f, "layer {}: {:?}", layer_id, checkpoints)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 impl SkipIndex {
-    pub fn open(mut data: OwnedBytes) -> SkipIndex {
+    /// Opens a skip index.
+    ///
+    /// Returns an `InvalidData` error if the layer offsets header
+    /// is truncated or inconsistent with the length of `data`.
+    /// An index without any checkpoint is valid and simply has no layers.
+    pub fn open(mut data: OwnedBytes) -> io::Result<SkipIndex> {
         let offsets: Vec<u64> = Vec::<VInt>::deserialize(&mut data)
-            .unwrap()
+            .map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Failed to read skip index layer offsets: {}", err),
+                )
+            })?
             .into_iter()
             .map(|el| el.0)
             .collect();
         let mut start_offset = 0;
         let mut layers = Vec::new();
         for end_offset in offsets {
+            if end_offset < start_offset || end_offset > data.len() as u64 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Skip index layer [{}..{}) is out of bounds (len={})",
+                        start_offset,
+                        end_offset,
+                        data.len()
+                    ),
+                ));
+            }
             let layer = Layer {
                 data: data.slice(start_offset as usize, end_offset as usize),
             };
             layers.push(layer);
             start_offset = end_offset;
         }
-        SkipIndex { layers }
+        Ok(SkipIndex { layers })
     }
 
     pub(crate) fn checkpoints(&self) -> impl Iterator<Item = Checkpoint> + '_ {
@@ -86,6 +128,25 @@ impl SkipIndex {
             .flat_map(|layer| layer.cursor())
     }
 
+    /// Returns the number of checkpoints in the data layer.
+    ///
+    /// The count is not persisted: it is obtained by decoding every block
+    /// of the data layer, which is linear in the number of checkpoints.
+    /// This method should not be called on a hot path. The number of
+    /// documents of a segment is available through `SegmentReader::max_doc`.
+    pub fn len(&self) -> usize {
+        self.checkpoints().count()
+    }
+
+    /// Returns true if the skip index does not contain any checkpoint.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints().next().is_none()
+    }
+
+    /// Returns the first checkpoint whose doc range ends after `target`,
+    /// that is the checkpoint containing `target` if any.
+    ///
+    /// Returns `None` if `target` is past the last checkpoint.
     pub fn seek(&self, target: DocId) -> Option<Checkpoint> {
         let first_layer_len = self
             .layers
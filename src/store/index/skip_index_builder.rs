@@ -11,6 +11,7 @@ use std::io::Write;
 struct LayerBuilder {
     buffer: Vec<u8>,
     pub block: CheckpointBlock,
+    period: usize,
 }
 
 impl LayerBuilder {
@@ -18,10 +19,12 @@ impl LayerBuilder {
         self.buffer
     }
 
-    fn new() -> LayerBuilder {
+    fn with_period(period: usize) -> LayerBuilder {
+        debug_assert!(period > 0);
         LayerBuilder {
             buffer: Vec::new(),
             block: CheckpointBlock::default(),
+            period,
         }
     }
 
@@ -52,7 +55,7 @@ impl LayerBuilder {
 
     fn insert(&mut self, checkpoint: Checkpoint) -> Option<Checkpoint> {
         self.push(checkpoint);
-        let emit_skip_info = self.block.len() >= CHECKPOINT_PERIOD;
+        let emit_skip_info = self.block.len() >= self.period;
         if emit_skip_info {
             self.flush_block()
         } else {
@@ -61,18 +64,56 @@ impl LayerBuilder {
     }
 }
 
+/// Builds a `SkipIndex` from the sequence of checkpoints of the doc store.
+///
+/// All of the layers are buffered in memory until `write` is called,
+/// because the layer offsets header precedes the layers.
+/// Checkpoints are delta-encoded, so the buffers only take a few bytes
+/// per checkpoint, that is per compressed store block.
 pub struct SkipIndexBuilder {
     layers: Vec<LayerBuilder>,
+    data_period: usize,
+    skip_period: usize,
 }
 
 impl SkipIndexBuilder {
     pub fn new() -> SkipIndexBuilder {
-        SkipIndexBuilder { layers: Vec::new() }
+        SkipIndexBuilder::with_periods(CHECKPOINT_PERIOD, CHECKPOINT_PERIOD)
+    }
+
+    /// Creates a builder whose data layer emits a skip checkpoint every
+    /// `data_period` checkpoints, and whose skip layers every `skip_period`.
+    ///
+    /// A large data period keeps the skip layers small, while a small skip
+    /// period keeps the number of checkpoints scanned per layer low during
+    /// `seek`, at the cost of more layers.
+    ///
+    /// Blocks are self-describing, so `SkipIndex::open` reads the result
+    /// regardless of the periods used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data_period` is 0 or if `skip_period` is lower than 2.
+    /// A skip period of 1 would promote every checkpoint to the next
+    /// layer, so that inserting a checkpoint would never terminate.
+    pub fn with_periods(data_period: usize, skip_period: usize) -> SkipIndexBuilder {
+        assert!(data_period > 0, "The data period must be at least 1.");
+        assert!(skip_period > 1, "The skip period must be at least 2.");
+        SkipIndexBuilder {
+            layers: Vec::new(),
+            data_period,
+            skip_period,
+        }
     }
 
     fn get_layer(&mut self, layer_id: usize) -> &mut LayerBuilder {
         if layer_id == self.layers.len() {
-            let layer_builder = LayerBuilder::new();
+            let period = if layer_id == 0 {
+                self.data_period
+            } else {
+                self.skip_period
+            };
+            let layer_builder = LayerBuilder::with_period(period);
             self.layers.push(layer_builder);
         }
         &mut self.layers[layer_id]
@@ -89,6 +130,47 @@ impl SkipIndexBuilder {
         }
     }
 
+    /// Returns the number of bytes `write` would emit, offsets header included.
+    ///
+    /// The pending blocks of each layer are serialized into a scratch
+    /// buffer, without modifying the builder.
+    pub fn serialized_len(&self) -> usize {
+        let mut last_pointer: Option<Checkpoint> = None;
+        let mut layer_lens = Vec::with_capacity(self.layers.len());
+        let mut block_buffer = Vec::new();
+        for skip_layer in &self.layers {
+            let mut block = CheckpointBlock {
+                checkpoints: skip_layer.block.checkpoints.clone(),
+            };
+            if let Some(checkpoint) = last_pointer {
+                block.push(checkpoint);
+            }
+            block_buffer.clear();
+            let start_offset = skip_layer.buffer.len() as u64;
+            last_pointer = block.doc_interval().map(|(start_doc, end_doc)| {
+                block.serialize(&mut block_buffer);
+                Checkpoint {
+                    start_doc,
+                    end_doc,
+                    start_offset,
+                    end_offset: start_offset + block_buffer.len() as u64,
+                }
+            });
+            layer_lens.push(skip_layer.buffer.len() + block_buffer.len());
+        }
+        let mut layer_offset = 0;
+        let mut layer_sizes = Vec::new();
+        for layer_len in layer_lens.iter().rev() {
+            layer_offset += *layer_len as u64;
+            layer_sizes.push(VInt(layer_offset));
+        }
+        let mut header = Vec::new();
+        layer_sizes
+            .serialize(&mut header)
+            .expect("Writing to a Vec<u8> cannot fail");
+        header.len() + layer_offset as usize
+    }
+
     pub fn write<W: Write>(mut self, output: &mut W) -> io::Result<()> {
         let mut last_pointer = None;
         for skip_layer in self.layers.iter_mut() {
@@ -69,11 +69,15 @@ pub mod tests {
     use tantivy_fst::Ulen;
 
     use super::*;
-    use crate::directory::{Directory, RAMDirectory, WritePtr};
+    use crate::common::{BinarySerializable, HasLen};
+    use crate::directory::{
+        AntiCallToken, Directory, RAMDirectory, TerminatingWrite, WritePtr,
+    };
     use crate::schema::Document;
     use crate::schema::FieldValue;
     use crate::schema::Schema;
     use crate::schema::TextOptions;
+    use std::io;
     use std::path::Path;
 
     pub fn write_lorem_ipsum_store(writer: WritePtr, num_docs: Ulen) -> Schema {
@@ -136,6 +140,65 @@ pub mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_store_compresses_repetitive_docs() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let body = schema_builder.add_text_field("body", TextOptions::default().set_stored());
+        let path = Path::new("store");
+        let directory = RAMDirectory::create();
+        let mut uncompressed_len = 0;
+        {
+            let mut store_writer = StoreWriter::new(directory.open_write(path)?);
+            for i in 0..1_000 {
+                let text = format!("{} {}", i, "the same words again and again ".repeat(20));
+                let doc = doc!(body=>text);
+                let mut serialized = Vec::new();
+                doc.serialize(&mut serialized)?;
+                uncompressed_len += serialized.len() as Ulen;
+                store_writer.store(&doc)?;
+            }
+            store_writer.close()?;
+        }
+        let store_file = directory.open_read(path)?;
+        assert!(store_file.len() * 4 < uncompressed_len);
+        let store = StoreReader::open(store_file)?;
+        assert_eq!(
+            store.get(567)?.get_first(body).unwrap().text().unwrap(),
+            format!("567 {}", "the same words again and again ".repeat(20))
+        );
+        Ok(())
+    }
+
+    struct FailingTerminateWriter;
+
+    impl io::Write for FailingTerminateWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl TerminatingWrite for FailingTerminateWriter {
+        fn terminate_ref(&mut self, _: AntiCallToken) -> io::Result<()> {
+            Err(io::Error::new(io::ErrorKind::Other, "terminate failed"))
+        }
+    }
+
+    #[test]
+    fn test_store_close_surfaces_write_errors() -> crate::Result<()> {
+        let mut schema_builder = Schema::builder();
+        let field_title =
+            schema_builder.add_text_field("title", TextOptions::default().set_stored());
+        let writer: WritePtr = io::BufWriter::new(Box::new(FailingTerminateWriter));
+        let mut store_writer = StoreWriter::new(writer);
+        store_writer.store(&doc!(field_title=>"Doc 0"))?;
+        assert!(store_writer.close().is_err());
+        Ok(())
+    }
 }
 
 #[cfg(all(test, feature = "unstable"))]
@@ -3,6 +3,7 @@ use super::index::SkipIndex;
 use crate::common::VInt;
 use crate::common::{BinarySerializable, HasLen};
 use crate::directory::{FileSlice, OwnedBytes};
+use crate::error::DataCorruption;
 use crate::schema::Document;
 use crate::space_usage::StoreSpaceUsage;
 use crate::store::index::Checkpoint;
@@ -28,6 +29,7 @@ pub struct StoreReader {
     cache_misses: Arc<AtomicUsize>,
     skip_index: Arc<SkipIndex>,
     space_usage: StoreSpaceUsage,
+    lossy_utf8: bool,
 }
 
 impl StoreReader {
@@ -36,7 +38,7 @@ impl StoreReader {
         let (data_file, offset_index_file) = split_file(store_file)?;
         let index_data = offset_index_file.read_bytes()?;
         let space_usage = StoreSpaceUsage::new(data_file.len(), offset_index_file.len());
-        let skip_index = SkipIndex::open(index_data);
+        let skip_index = SkipIndex::open(index_data)?;
         Ok(StoreReader {
             data: data_file,
             cache: Arc::new(Mutex::new(LruCache::new(LRU_CACHE_CAPACITY as usize))),
@@ -44,9 +46,20 @@ impl StoreReader {
             cache_misses: Default::default(),
             skip_index: Arc::new(skip_index),
             space_usage,
+            lossy_utf8: false,
         })
     }
 
+    /// Sets how text values that are not valid UTF-8 are handled.
+    ///
+    /// By default, reading such a document returns a `DataCorruption` error.
+    /// If `lossy_utf8` is set, invalid sequences are replaced by
+    /// `U+FFFD REPLACEMENT CHARACTER` instead.
+    pub fn with_lossy_utf8(mut self, lossy_utf8: bool) -> StoreReader {
+        self.lossy_utf8 = lossy_utf8;
+        self
+    }
+
     pub(crate) fn block_checkpoints(&self) -> impl Iterator<Item = Checkpoint> + '_ {
         self.skip_index.checkpoints()
     }
@@ -118,7 +131,22 @@ impl StoreReader {
 
         let doc_length = VInt::deserialize(&mut cursor)?.val() as usize;
         cursor = &cursor[..doc_length];
-        Ok(Document::deserialize(&mut cursor)?)
+        let doc_res = if self.lossy_utf8 {
+            Document::deserialize_lossy_utf8(&mut cursor)
+        } else {
+            Document::deserialize(&mut cursor)
+        };
+        doc_res.map_err(|io_err| {
+            if io_err.kind() == io::ErrorKind::InvalidData {
+                DataCorruption::comment_only(format!(
+                    "Failed to deserialize Doc #{}: {}",
+                    doc_id, io_err
+                ))
+                .into()
+            } else {
+                crate::TantivyError::from(io_err)
+            }
+        })
     }
 
     /// Reads the given document ids.
@@ -220,4 +248,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_store_reader_reads_segment_store() -> crate::Result<()> {
+        use crate::schema::{Schema, STORED, TEXT};
+        use crate::Index;
+        let mut schema_builder = Schema::builder();
+        let title = schema_builder.add_text_field("title", TEXT | STORED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut index_writer = index.writer_for_tests()?;
+        for i in 0..300 {
+            index_writer.add_document(doc!(title=>format!("Doc {}", i)));
+        }
+        index_writer.commit()?;
+        let searcher = index.reader()?.searcher();
+        assert_eq!(searcher.segment_readers().len(), 1);
+        let store = searcher.segment_reader(0).get_store_reader()?;
+        for doc_id in 0..300 {
+            let doc = store.get(doc_id)?;
+            assert_eq!(
+                get_text_field(&doc, &title),
+                Some(format!("Doc {}", doc_id).as_str())
+            );
+        }
+        assert!(store.get(300).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_store_invalid_utf8() -> crate::Result<()> {
+        let directory = RAMDirectory::create();
+        let path = Path::new("store");
+        let writer = directory.open_write(path)?;
+        let schema = write_lorem_ipsum_store(writer, 1);
+        let title = schema.get_field("title").unwrap();
+        let store_file = directory.open_read(path)?;
+        let store = StoreReader::open(store_file)?;
+        // Corrupt the first byte of the title in the decompressed block.
+        let checkpoint = store.block_checkpoint(0).unwrap();
+        let mut block: Vec<u8> = store.read_block(&checkpoint)?.to_vec();
+        let title_pos = block
+            .windows(5)
+            .position(|window| window == b"Doc 0")
+            .unwrap();
+        block[title_pos] = 0xFF;
+        store
+            .cache
+            .lock()
+            .unwrap()
+            .put(checkpoint.start_offset, Arc::new(block));
+
+        assert!(matches!(
+            store.get(0),
+            Err(crate::TantivyError::DataCorruption(_))
+        ));
+        let store = store.with_lossy_utf8(true);
+        let doc = store.get(0)?;
+        assert_eq!(get_text_field(&doc, &title), Some("\u{FFFD}oc 0"));
+        Ok(())
+    }
 }
@@ -196,6 +196,17 @@ impl TermDictionary {
         TermStreamerBuilder::new(self, self.fst_index.range())
     }
 
+    /// Returns a range builder, to stream all of the terms
+    /// starting with `prefix`, in sorted order.
+    pub fn prefix_range<K: AsRef<[u8]>>(&self, prefix: K) -> TermStreamerBuilder<'_> {
+        let prefix = prefix.as_ref();
+        let range = self.range().ge(prefix);
+        match prefix_upper_bound(prefix) {
+            Some(upper_bound) => range.lt(upper_bound),
+            None => range,
+        }
+    }
+
     /// A stream of all the sorted terms. [See also `.stream_field()`](#method.stream_field)
     pub fn stream(&self) -> io::Result<TermStreamer<'_>> {
         self.range().into_stream()
@@ -208,3 +219,16 @@ impl TermDictionary {
         TermStreamerBuilder::<A>::new(self, stream_builder)
     }
 }
+
+/// Returns the smallest key greater than all of the keys starting with `prefix`,
+/// or `None` if there is no such key (e.g. if the prefix only contains `0xFF` bytes).
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut upper_bound = prefix.to_vec();
+    while let Some(last_byte) = upper_bound.pop() {
+        if last_byte < u8::max_value() {
+            upper_bound.push(last_byte + 1);
+            return Some(upper_bound);
+        }
+    }
+    None
+}
@@ -391,6 +391,92 @@ fn test_stream_term_ord() -> crate::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_stream_range_fixed_width_integers() -> crate::Result<()> {
+    let key = |val: u64| val.to_be_bytes();
+    let buffer: Vec<u8> = {
+        let mut term_dictionary_builder = TermDictionaryBuilder::create(vec![])?;
+        for val in (0u64..1_000).map(|i| i * 3) {
+            term_dictionary_builder.insert(key(val), &make_term_info(val))?;
+        }
+        term_dictionary_builder.finish()?
+    };
+    let term_dictionary: TermDictionary = TermDictionary::open(FileSlice::from(buffer))?;
+    let stream_vals = |mut stream: TermStreamer| {
+        let mut vals = Vec::new();
+        while let Some((key, term_info)) = stream.next() {
+            let mut val_bytes = [0u8; 8];
+            val_bytes.copy_from_slice(key);
+            let val = u64::from_be_bytes(val_bytes);
+            assert_eq!(term_info, &make_term_info(val));
+            vals.push(val);
+        }
+        vals
+    };
+    let inclusive = term_dictionary
+        .range()
+        .ge(key(300))
+        .le(key(330))
+        .into_stream()?;
+    assert_eq!(stream_vals(inclusive), (100..=110).map(|i| i * 3).collect::<Vec<u64>>());
+    let exclusive = term_dictionary
+        .range()
+        .gt(key(300))
+        .lt(key(330))
+        .into_stream()?;
+    assert_eq!(stream_vals(exclusive), (101..110).map(|i| i * 3).collect::<Vec<u64>>());
+    let between_terms = term_dictionary
+        .range()
+        .ge(key(301))
+        .lt(key(305))
+        .into_stream()?;
+    assert_eq!(stream_vals(between_terms), vec![303]);
+    Ok(())
+}
+
+#[test]
+fn test_prefix_range() -> crate::Result<()> {
+    let keys: [&[u8]; 8] = [
+        b"",
+        b"car",
+        b"card",
+        b"cart",
+        b"cas",
+        b"dog",
+        b"\xFF",
+        b"\xFF\xFFa",
+    ];
+    let buffer: Vec<u8> = {
+        let mut term_dictionary_builder = TermDictionaryBuilder::create(vec![])?;
+        for (term_ord, key) in keys.iter().enumerate() {
+            term_dictionary_builder.insert(key, &make_term_info(term_ord as u64))?;
+        }
+        term_dictionary_builder.finish()?
+    };
+    let term_dictionary: TermDictionary = TermDictionary::open(FileSlice::from(buffer))?;
+    let prefix_keys = |prefix: &[u8]| -> crate::Result<Vec<Vec<u8>>> {
+        let mut stream = term_dictionary.prefix_range(prefix).into_stream()?;
+        let mut keys = Vec::new();
+        while let Some((key, _)) = stream.next() {
+            keys.push(key.to_vec());
+        }
+        Ok(keys)
+    };
+    assert_eq!(
+        prefix_keys(b"car")?,
+        vec![b"car".to_vec(), b"card".to_vec(), b"cart".to_vec()]
+    );
+    assert_eq!(prefix_keys(b"card")?, vec![b"card".to_vec()]);
+    assert_eq!(prefix_keys(b"d")?, vec![b"dog".to_vec()]);
+    assert!(prefix_keys(b"e")?.is_empty());
+    assert_eq!(
+        prefix_keys(b"\xFF")?,
+        vec![b"\xFF".to_vec(), b"\xFF\xFFa".to_vec()]
+    );
+    assert_eq!(prefix_keys(b"")?.len(), keys.len());
+    Ok(())
+}
+
 #[test]
 fn test_automaton_search() -> crate::Result<()> {
     use crate::query::DFAWrapper;
@@ -0,0 +1,114 @@
+use crate::tokenizer::{
+    AlphaNumOnlyFilter, AsciiFoldingFilter, BoxTokenFilter, Language, LowerCaser, RemoveLongFilter,
+    Stemmer, StopWordFilter,
+};
+use serde::{Deserialize, Serialize};
+
+/// Serializable description of a `TextAnalyzer` pipeline.
+///
+/// An `AnalyzerConfig` is persisted with the schema (see
+/// [`TextFieldIndexing::set_analyzer`](../schema/struct.TextFieldIndexing.html#method.set_analyzer)),
+/// so that a field is analyzed the same way across process restarts.
+///
+/// The pipeline starts with the tokenizer registered under `tokenizer`
+/// in the `TokenizerManager`, and then applies `filters` in order.
+///
+/// ```rust
+/// use tantivy::tokenizer::{AnalyzerConfig, Language, TokenFilterConfig};
+///
+/// let config = AnalyzerConfig::new("raw")
+///     .filter(TokenFilterConfig::LowerCaser)
+///     .filter(TokenFilterConfig::Stemmer(Language::English));
+/// assert_eq!(config.tokenizer(), "raw");
+/// assert_eq!(config.filters().len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnalyzerConfig {
+    tokenizer: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    filters: Vec<TokenFilterConfig>,
+}
+
+impl AnalyzerConfig {
+    /// Creates an `AnalyzerConfig` starting with the tokenizer
+    /// registered under `tokenizer_name`.
+    pub fn new(tokenizer_name: &str) -> AnalyzerConfig {
+        AnalyzerConfig {
+            tokenizer: tokenizer_name.to_string(),
+            filters: Vec::new(),
+        }
+    }
+
+    /// Appends a token filter to the pipeline.
+    pub fn filter(mut self, token_filter: TokenFilterConfig) -> AnalyzerConfig {
+        self.filters.push(token_filter);
+        self
+    }
+
+    /// Returns the name of the tokenizer the pipeline starts with.
+    pub fn tokenizer(&self) -> &str {
+        &self.tokenizer
+    }
+
+    /// Returns the token filters, in the order they are applied.
+    pub fn filters(&self) -> &[TokenFilterConfig] {
+        &self.filters
+    }
+}
+
+/// Serializable description of one of tantivy's built-in `TokenFilter`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "filter", content = "params", rename_all = "snake_case")]
+pub enum TokenFilterConfig {
+    /// See [`LowerCaser`](./struct.LowerCaser.html).
+    LowerCaser,
+    /// See [`AsciiFoldingFilter`](./struct.AsciiFoldingFilter.html).
+    AsciiFolding,
+    /// See [`AlphaNumOnlyFilter`](./struct.AlphaNumOnlyFilter.html).
+    AlphaNumOnly,
+    /// See [`RemoveLongFilter`](./struct.RemoveLongFilter.html).
+    RemoveLong(usize),
+    /// See [`Stemmer`](./struct.Stemmer.html).
+    Stemmer(Language),
+    /// See [`StopWordFilter`](./struct.StopWordFilter.html).
+    StopWords(Vec<String>),
+}
+
+impl TokenFilterConfig {
+    pub(crate) fn build(&self) -> BoxTokenFilter {
+        match self {
+            TokenFilterConfig::LowerCaser => LowerCaser.into(),
+            TokenFilterConfig::AsciiFolding => AsciiFoldingFilter.into(),
+            TokenFilterConfig::AlphaNumOnly => AlphaNumOnlyFilter.into(),
+            TokenFilterConfig::RemoveLong(length_limit) => {
+                RemoveLongFilter::limit(*length_limit).into()
+            }
+            TokenFilterConfig::Stemmer(language) => Stemmer::new(*language).into(),
+            TokenFilterConfig::StopWords(words) => StopWordFilter::remove(words.clone()).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnalyzerConfig, TokenFilterConfig};
+    use crate::tokenizer::Language;
+
+    #[test]
+    fn test_analyzer_config_serialization() {
+        let config = AnalyzerConfig::new("default")
+            .filter(TokenFilterConfig::AsciiFolding)
+            .filter(TokenFilterConfig::RemoveLong(20))
+            .filter(TokenFilterConfig::Stemmer(Language::French))
+            .filter(TokenFilterConfig::StopWords(vec!["le".to_string()]));
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tokenizer":"default","filters":[{"filter":"ascii_folding"},{"filter":"remove_long","params":20},{"filter":"stemmer","params":"French"},{"filter":"stop_words","params":["le"]}]}"#
+        );
+        let deserialized: AnalyzerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, config);
+        let no_filters: AnalyzerConfig = serde_json::from_str(r#"{"tokenizer":"raw"}"#).unwrap();
+        assert_eq!(no_filters, AnalyzerConfig::new("raw"));
+    }
+}
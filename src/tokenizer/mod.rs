@@ -118,6 +118,7 @@
 //! ```
 //!
 mod alphanum_only;
+mod analyzer_config;
 mod ascii_folding_filter;
 mod facet_tokenizer;
 mod lower_caser;
@@ -133,6 +134,7 @@ mod tokenizer;
 mod tokenizer_manager;
 
 pub use self::alphanum_only::AlphaNumOnlyFilter;
+pub use self::analyzer_config::{AnalyzerConfig, TokenFilterConfig};
 pub use self::ascii_folding_filter::AsciiFoldingFilter;
 pub use self::facet_tokenizer::FacetTokenizer;
 pub use self::lower_caser::LowerCaser;
@@ -1,10 +1,12 @@
 use crate::tokenizer::stemmer::Language;
 use crate::tokenizer::tokenizer::TextAnalyzer;
+use crate::tokenizer::AnalyzerConfig;
 use crate::tokenizer::LowerCaser;
 use crate::tokenizer::RawTokenizer;
 use crate::tokenizer::RemoveLongFilter;
 use crate::tokenizer::SimpleTokenizer;
 use crate::tokenizer::Stemmer;
+use crate::TantivyError;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
@@ -20,9 +22,15 @@ use std::sync::{Arc, RwLock};
 ///  * `en_stem` : Like `default`, but also applies stemming on the
 ///  resulting tokens. Stemming can improve the recall of your
 ///  search engine.
+///
+/// The analyzers configured in the schema of an index (see
+/// [`TextFieldIndexing::set_analyzer`](../schema/struct.TextFieldIndexing.html#method.set_analyzer))
+/// are built when they are looked up, so that they may start with a tokenizer
+/// registered after the index was opened.
 #[derive(Clone)]
 pub struct TokenizerManager {
     tokenizers: Arc<RwLock<HashMap<String, TextAnalyzer>>>,
+    analyzers: Arc<RwLock<HashMap<String, AnalyzerConfig>>>,
 }
 
 impl TokenizerManager {
@@ -38,14 +46,63 @@ impl TokenizerManager {
             .insert(tokenizer_name.to_string(), boxed_tokenizer);
     }
 
+    /// Registers the analyzer described by `analyzer_config` under `tokenizer_name`.
+    ///
+    /// The analyzer is only built when it is looked up.
+    pub(crate) fn register_analyzer(&self, tokenizer_name: &str, analyzer_config: AnalyzerConfig) {
+        self.analyzers
+            .write()
+            .expect("Acquiring the lock should never fail")
+            .insert(tokenizer_name.to_string(), analyzer_config);
+    }
+
     /// Accessing a tokenizer given its name.
+    ///
+    /// Returns `None` if no tokenizer is registered under this name, or if it
+    /// is a configured analyzer whose tokenizer is not registered.
     pub fn get(&self, tokenizer_name: &str) -> Option<TextAnalyzer> {
+        let analyzer_config_opt = self
+            .analyzers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .get(tokenizer_name)
+            .cloned();
+        if let Some(analyzer_config) = analyzer_config_opt {
+            return self.build(&analyzer_config).ok();
+        }
         self.tokenizers
             .read()
             .expect("Acquiring the lock should never fail")
             .get(tokenizer_name)
             .cloned()
     }
+
+    /// Builds the `TextAnalyzer` described by an `AnalyzerConfig`.
+    ///
+    /// The pipeline starts with a tokenizer registered with `register`: it cannot
+    /// start with another configured analyzer.
+    ///
+    /// Returns an error if the config refers to a tokenizer that is not registered.
+    pub fn build(&self, analyzer_config: &AnalyzerConfig) -> crate::Result<TextAnalyzer> {
+        let text_analyzer_opt = self
+            .tokenizers
+            .read()
+            .expect("Acquiring the lock should never fail")
+            .get(analyzer_config.tokenizer())
+            .cloned();
+        let text_analyzer = text_analyzer_opt.ok_or_else(|| {
+            TantivyError::SchemaError(format!(
+                "Unknown tokenizer {:?} in analyzer config.",
+                analyzer_config.tokenizer()
+            ))
+        })?;
+        Ok(analyzer_config
+            .filters()
+            .iter()
+            .fold(text_analyzer, |text_analyzer, token_filter| {
+                text_analyzer.filter(token_filter.build())
+            }))
+    }
 }
 
 impl Default for TokenizerManager {
@@ -57,6 +114,7 @@ impl Default for TokenizerManager {
     fn default() -> TokenizerManager {
         let manager = TokenizerManager {
             tokenizers: Arc::new(RwLock::new(HashMap::new())),
+            analyzers: Arc::new(RwLock::new(HashMap::new())),
         };
         manager.register("raw", RawTokenizer);
         manager.register(